@@ -0,0 +1,762 @@
+use crate::state::{combine_keys, State};
+use crate::{register, AS1115Error, BlinkFrequency, BlinkPhase, ClockSource, DecodeType};
+use crate::{
+    DigitMap, Font, FormatOptions, KeyState, LedFaults, LedTest, LetterCase, NoIrq, Rotation,
+    SegmentMap, WriteMode, DEFAULT_ADDRESS, MAX_DIGITS,
+};
+use embedded_hal::digital::InputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+use num_traits::ToPrimitive;
+
+/// Async AS1115 driver built on `embedded_hal_async::i2c::I2c`, with the same API as the blocking `AS1115` driver.
+pub struct AS1115Async<I2C, const NUM_DIGITS: u8, IRQ = NoIrq> {
+    pub i2c: I2C,
+    pub address: u8,
+    irq: IRQ,
+    state: State<NUM_DIGITS>,
+}
+
+impl<I2C, E, const NUM_DIGITS: u8> AS1115Async<I2C, NUM_DIGITS>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new AS1115Async instance with the given I2C interface.
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            address: DEFAULT_ADDRESS,
+            irq: NoIrq,
+            state: State::new(),
+        }
+    }
+
+    /// Create a new AS1115Async instance with the given I2C interface and address (using self-addressing with KEYA, SEGF, and SEGG pins).
+    pub fn new_with_addr(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            irq: NoIrq,
+            state: State::new(),
+        }
+    }
+
+    /// Use the given pin connected to the AS1115's IRQ output to only read keyscan data when it changed.
+    pub fn with_irq<IRQ>(self, irq: IRQ) -> AS1115Async<I2C, NUM_DIGITS, IRQ> {
+        AS1115Async {
+            i2c: self.i2c,
+            address: self.address,
+            irq,
+            state: self.state,
+        }
+    }
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, IRQ> AS1115Async<I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Destroy the AS1115Async instance and return the underlying I2C interface.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Destroy the AS1115Async instance and return the underlying I2C interface and IRQ pin.
+    pub fn destroy_with_irq(self) -> (I2C, IRQ) {
+        (self.i2c, self.irq)
+    }
+
+    /// Initialize the AS1115 with the given global intensity.
+    pub async fn init(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        self.write_register_to_addr(
+            DEFAULT_ADDRESS,
+            register::SHUTDOWN_MODE,
+            register::shutdown_mode::NORMAL_OPERATION | register::shutdown_mode::RESET_FEATURE,
+        )
+        .await?;
+
+        if self.address != DEFAULT_ADDRESS {
+            self.write_register_to_addr(
+                DEFAULT_ADDRESS,
+                register::SELF_ADDRESSING,
+                register::self_addressing::USER_SET_ADDR,
+            )
+            .await?;
+        }
+
+        self.write_register(register::DECODE_MODE, self.state.decode_mask)
+            .await?;
+        self.write_register(register::SCAN_LIMIT, NUM_DIGITS - 1)
+            .await?;
+        self.set_intensity(intensity).await?;
+
+        // feature register was reset above, restore any settings made before init
+        if self.state.feature != 0 {
+            self.write_register(register::FEATURE, self.state.feature)
+                .await?;
+        }
+
+        // digit registers are undefined after power-up, make sure the next flush writes all of them
+        self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+
+        Ok(())
+    }
+
+    /// Put the AS1115 into shutdown mode, turning the display off.
+    /// Digit data and intensity settings are kept and shown again on `wake`.
+    /// If preserve_feature is false, the feature register (blink, clock and decode font settings) is reset to defaults.
+    pub async fn shutdown(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.write_shutdown_mode(register::shutdown_mode::SHUTDOWN_MODE, preserve_feature)
+            .await
+    }
+
+    /// Wake the AS1115 from shutdown mode, resuming normal operation.
+    /// If preserve_feature is false, the feature register (blink, clock and decode font settings) is reset to defaults.
+    pub async fn wake(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.write_shutdown_mode(register::shutdown_mode::NORMAL_OPERATION, preserve_feature)
+            .await
+    }
+
+    /// Reset all control registers except the feature register to their power-up defaults.
+    /// If reapply is true, the known configuration (normal operation, decode mode, scan limit, intensities and digit data) is written again afterwards.
+    /// Otherwise the AS1115 is left in its default state, which is shutdown mode.
+    /// A self-addressed AS1115 falls back to DEFAULT_ADDRESS on reset and is re-provisioned with its address as in `init`.
+    pub async fn reset_registers(&mut self, reapply: bool) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::FEATURE,
+            self.state.feature | register::feature::REG_RESET,
+        )
+        .await?;
+
+        if self.address != DEFAULT_ADDRESS {
+            self.write_register_to_addr(
+                DEFAULT_ADDRESS,
+                register::SELF_ADDRESSING,
+                register::self_addressing::USER_SET_ADDR,
+            )
+            .await?;
+        }
+
+        self.write_register(register::FEATURE, self.state.feature)
+            .await?;
+
+        if !reapply {
+            // the digit registers no longer match the shadow buffer
+            self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+            return Ok(());
+        }
+
+        self.write_register(
+            register::SHUTDOWN_MODE,
+            register::shutdown_mode::NORMAL_OPERATION | register::shutdown_mode::PRESERVE_FEATURE,
+        )
+        .await?;
+        self.write_register(register::DECODE_MODE, self.state.decode_mask)
+            .await?;
+        self.write_register(register::SCAN_LIMIT, NUM_DIGITS - 1)
+            .await?;
+        self.write_register(register::GLOBAL_INTENSITY, self.state.intensity[0])
+            .await?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i).await?;
+        }
+        let digits = self.state.register_data();
+        self.write_registers(register::DIGIT_OFFSET, &digits[..NUM_DIGITS as usize])
+            .await?;
+        self.state.dirty = 0;
+        Ok(())
+    }
+
+    /// Set whether digit updates are written to the AS1115 immediately or buffered until `flush`.
+    /// In buffered mode only digits whose data changed are written on flush.
+    pub fn set_write_mode(&mut self, write_mode: WriteMode) {
+        self.state.write_mode = write_mode;
+    }
+
+    /// Enable or disable burst writes, which update consecutive digit registers in a single I2C transaction using the AS1115's register auto-increment.
+    /// Enabled by default, when disabled every digit register is written in its own transaction.
+    pub fn set_burst_writes(&mut self, enable: bool) {
+        self.state.burst_writes = enable;
+    }
+
+    /// Set whether ASCII text is displayed with distinct upper and lower case letter glyphs.
+    /// Applies to text displayed afterwards with the built-in ASCII font, a custom font set with `set_font` is left in place.
+    pub fn set_letter_case(&mut self, letter_case: LetterCase) {
+        self.state.set_letter_case(letter_case);
+    }
+
+    /// Set the font used to display text, replacing the built-in ASCII font and its letter case mode.
+    /// Applies to text displayed afterwards.
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        self.state.font = font;
+    }
+
+    /// Set the segment wiring of the board, applied to every digit without hardware decoding.
+    /// Rewrites all digits with the new wiring.
+    pub async fn set_segment_map(&mut self, segment_map: SegmentMap) -> Result<(), AS1115Error<E>> {
+        self.state.segment_map = segment_map;
+        self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+        self.commit().await
+    }
+
+    /// Set which DIGIT register drives each logical digit position, e.g. `DigitMap::reversed(NUM_DIGITS)` for modules wired right to left.
+    /// Moves the current digit data and intensities to their new registers.
+    /// Returns InvalidValue if a position below NUM_DIGITS maps to a register beyond NUM_DIGITS.
+    pub async fn set_digit_map(&mut self, digit_map: DigitMap) -> Result<(), AS1115Error<E>> {
+        self.state.set_layout(digit_map, self.state.rotation)?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i).await?;
+        }
+        self.commit().await
+    }
+
+    /// Set the orientation the display is mounted in, applied to every digit write on top of the digit map.
+    /// Returns InvalidValue when turning the display upside down while hardware decoding is enabled, as the decoder font can't be rotated.
+    pub async fn set_rotation(&mut self, rotation: Rotation) -> Result<(), AS1115Error<E>> {
+        self.state.set_layout(self.state.digit_map, rotation)?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i).await?;
+        }
+        self.commit().await
+    }
+
+    /// Write all changed digits from the shadow buffer to the AS1115.
+    pub async fn flush(&mut self) -> Result<(), AS1115Error<E>> {
+        // write each run of consecutive dirty digits together
+        let mut next = 0;
+        while let Some((start, end)) = self.state.next_dirty_run(next) {
+            let digits = self.state.register_data();
+            self.write_registers(
+                register::DIGIT_OFFSET + start,
+                &digits[start as usize..end as usize],
+            )
+            .await?;
+            self.state.mark_clean(start, end);
+            next = end;
+        }
+        Ok(())
+    }
+
+    /// Clear all digit data.
+    pub async fn clear(&mut self) -> Result<(), AS1115Error<E>> {
+        self.state.clear();
+        self.commit().await
+    }
+
+    /// Display best-effort ASCII characters on the seven-segment display.
+    /// Skips over any characters that do not have a valid segment mapping.
+    /// Decimal points are included using the seven-segment display's DP segment.
+    /// Truncates the input to fit NUM_DIGITS.
+    /// Text is sent as segment data, which digits with hardware decoding show as decoder glyphs instead, so keep text on digits without decoding.
+    pub async fn display_ascii(&mut self, chars: &[u8]) -> Result<(), AS1115Error<E>> {
+        self.state.display_ascii(chars);
+        self.commit().await
+    }
+
+    /// Display an integer value in decimal format on the seven-segment display.
+    /// Accepts any integer type, the full i64 and u64 ranges are supported.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the value is too large to fit in the display.
+    pub async fn display_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 10, &FormatOptions::new())?;
+        self.commit().await
+    }
+
+    /// Display an integer value in decimal format laid out according to the given options, see `display_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub async fn display_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 10, options)?;
+        self.commit().await
+    }
+
+    /// Display an integer value in hexadecimal format on the seven-segment display.
+    /// Accepts any integer type, the full i64 and u64 ranges are supported.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the number is too large to fit in the display.
+    pub async fn display_hex_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 16, &FormatOptions::new())?;
+        self.commit().await
+    }
+
+    /// Display an integer value in hexadecimal format laid out according to the given options, see `display_hex_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub async fn display_hex_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 16, options)?;
+        self.commit().await
+    }
+
+    /// Display an integer value in the given base from 2 to 16 on the seven-segment display, using the hex digit glyphs above 9.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the base is invalid or the value is too large to fit in the display.
+    pub async fn display_radix<T>(&mut self, value: T, base: u32) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state
+            .display_radix(value, base, &FormatOptions::new())?;
+        self.commit().await
+    }
+
+    /// Display an integer value in the given base laid out according to the given options, see `display_radix`.
+    /// Returns InvalidValue if the base is invalid or the value or the minimum width won't fit in the display.
+    pub async fn display_radix_with<T>(
+        &mut self,
+        value: T,
+        base: u32,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, base, options)?;
+        self.commit().await
+    }
+
+    /// Display an integer value in binary format on the seven-segment display, see `display_radix`.
+    pub async fn display_binary<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 2).await
+    }
+
+    /// Display an integer value in octal format on the seven-segment display, see `display_radix`.
+    pub async fn display_octal<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 8).await
+    }
+
+    /// Display a fixed-point value, value / 10^decimals, on the seven-segment display using only integer arithmetic.
+    /// The DP is placed after the units digit, values below 1 get a leading 0, and decimals that don't fit are rounded off.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the integer part doesn't fit in the display.
+    pub async fn display_fixed(&mut self, value: i32, decimals: u8) -> Result<(), AS1115Error<E>> {
+        self.state
+            .display_fixed(value, decimals, &FormatOptions::new())?;
+        self.commit().await
+    }
+
+    /// Display a fixed-point value laid out according to the given options, see `display_fixed`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub async fn display_fixed_with(
+        &mut self,
+        value: i32,
+        decimals: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.display_fixed(value, decimals, options)?;
+        self.commit().await
+    }
+
+    /// Display a floating-point decimal value on the seven-segment display.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the value won't fit with the given precision or if the precision value is invalid (0 or > NUM_DIGITS).
+    #[cfg(feature = "display_float_value")]
+    pub async fn display_float_value<T>(
+        &mut self,
+        value: T,
+        precision: u8,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state
+            .display_float_value(value, precision, &FormatOptions::new())?;
+        self.commit().await
+    }
+
+    /// Display a floating-point decimal value laid out according to the given options, see `display_float_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    #[cfg(feature = "display_float_value")]
+    pub async fn display_float_value_with<T>(
+        &mut self,
+        value: T,
+        precision: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_float_value(value, precision, options)?;
+        self.commit().await
+    }
+
+    /// Display raw segment data on the seven-segment display.
+    /// Truncates extra segment data beyond NUM_DIGITS.
+    pub async fn display_segments(&mut self, segments: &[u8]) -> Result<(), AS1115Error<E>> {
+        self.state.display_segments(segments);
+        self.commit().await
+    }
+
+    /// Read keyscan data from 16 keys.
+    /// Returns the raw KEYA and KEYB register values in the high and low byte, see `read_key_state` for a typed result.
+    pub async fn read_keys(&mut self) -> Result<u16, AS1115Error<E>> {
+        let key_a = self.read_register(register::KEY_A).await?;
+        let key_b = self.read_register(register::KEY_B).await?;
+        Ok(combine_keys(key_a, key_b, self.address != DEFAULT_ADDRESS))
+    }
+
+    /// Read the set of pressed keys.
+    /// Keys shared with the self-addressing pins are never reported as pressed if self-addressing is used.
+    pub async fn read_key_state(&mut self) -> Result<KeyState, AS1115Error<E>> {
+        let key_a = self.read_register(register::KEY_A).await?;
+        let key_b = self.read_register(register::KEY_B).await?;
+        Ok(KeyState::from_registers(
+            key_a,
+            key_b,
+            self.address != DEFAULT_ADDRESS,
+        ))
+    }
+
+    /// Set a specific digit to display an ASCII character.
+    /// Returns InvalidLocation if the digit index is out of bounds.
+    /// Returns InvalidValue if the character does not have a valid segment mapping.
+    pub async fn set_digit_ascii_char(
+        &mut self,
+        digit: u8,
+        char: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.set_digit_ascii_char(digit, char)?;
+        self.commit().await
+    }
+
+    /// Set a specific digit to display a hexadecimal digit.
+    /// Returns InvalidLocation if the digit index is out of bounds.
+    /// Returns InvalidValue if the value is not a valid hexadecimal digit (0-15), or if the digit uses Code-B decoding and the value is above 9.
+    pub async fn set_digit_hex_value(
+        &mut self,
+        digit: u8,
+        value: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.set_digit_hex_value(digit, value)?;
+        self.commit().await
+    }
+
+    /// Set a specific digit to display custom segment data.
+    /// For digits with hardware decoding enabled the data is sent as-is and interpreted as a decoder code.
+    /// Returns InvalidLocation if the digit index is out of bounds.
+    pub async fn set_digit_segment_data(
+        &mut self,
+        digit: u8,
+        segment_data: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.set_digit_segment_data(digit, segment_data)?;
+        self.commit().await
+    }
+
+    /// Set a specific digit to display a decimal digit (0-9).
+    /// Returns InvalidLocation if the digit index is out of bounds.
+    /// Returns InvalidValue if the value is not a valid decimal digit (0-9).
+    pub async fn set_digit_value(&mut self, digit: u8, value: u8) -> Result<(), AS1115Error<E>> {
+        self.state.set_digit_value(digit, value)?;
+        self.commit().await
+    }
+
+    /// Set the global intensity for all digits.
+    /// Returns InvalidValue if the intensity value is out of range.
+    pub async fn set_intensity(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        self.state.set_intensity(intensity)?;
+        self.write_register(register::GLOBAL_INTENSITY, intensity)
+            .await?;
+        Ok(())
+    }
+
+    /// Set the intensity for a specific digit.
+    /// Returns InvalidLocation if the digit index is out of bounds.
+    /// Returns InvalidValue if the intensity value is out of range.
+    pub async fn set_digit_intensity(
+        &mut self,
+        digit: u8,
+        intensity: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        let digit = self.state.set_digit_intensity(digit, intensity)?;
+        self.write_digit_intensity(digit).await
+    }
+
+    /// Enable or disable display test mode (all LED segments on).
+    pub async fn set_display_test(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        let mut test_mode = self.read_register(register::DISPLAY_TEST_MODE).await?;
+
+        if enable {
+            test_mode |= register::display_test_mode::DISP_TEST;
+        } else {
+            test_mode &= !register::display_test_mode::DISP_TEST;
+        }
+
+        self.write_register(register::DISPLAY_TEST_MODE, test_mode)
+            .await?;
+        Ok(())
+    }
+
+    /// Enable hardware decoding for the digits set in the mask (bit 0 for digit 0) using the given decoder font.
+    /// Numeric display functions send decoder codes instead of segment data for decoded digits.
+    /// HEX decoding has no minus sign or blank glyph, so negative values are rejected and unused leading digits show 0.
+    /// Digits switching between decoded and segment data, or to another decoder font, are blanked and all digits are rewritten.
+    /// Returns InvalidValue if the mask contains digits beyond NUM_DIGITS, or any digits while the display is upside down.
+    pub async fn set_decode_mode(
+        &mut self,
+        mask: u8,
+        decode_type: DecodeType,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.check_decode_mask(mask)?;
+        let hex = decode_type == DecodeType::Hex;
+        let decoder_changed = (self.state.feature & register::feature::DECODE_SET != 0) != hex;
+        self.update_feature(register::feature::DECODE_SET, hex)
+            .await?;
+        self.write_register(register::DECODE_MODE, mask).await?;
+        self.state.set_decode_mask(mask, decoder_changed);
+        self.commit().await
+    }
+
+    /// Select the internal oscillator or the external CLK pin as system clock.
+    pub async fn set_clock_source(&mut self, source: ClockSource) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::CLK_EN, source == ClockSource::External)
+            .await
+    }
+
+    /// Returns the currently selected system clock source.
+    pub fn clock_source(&self) -> ClockSource {
+        self.state.clock_source()
+    }
+
+    /// Enable or disable hardware blinking of the whole display.
+    pub async fn set_blink(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::BLINK_EN, enable)
+            .await
+    }
+
+    /// Set the hardware blink frequency.
+    pub async fn set_blink_frequency(
+        &mut self,
+        frequency: BlinkFrequency,
+    ) -> Result<(), AS1115Error<E>> {
+        self.update_feature(
+            register::feature::BLINK_FREQ_SET,
+            frequency == BlinkFrequency::Slow,
+        )
+        .await
+    }
+
+    /// Returns the typical blink period in milliseconds for the selected blink frequency.
+    /// Returns None when the external clock is selected, as the period then depends on the external clock frequency.
+    pub fn blink_period_ms(&self) -> Option<u16> {
+        self.state.blink_period_ms()
+    }
+
+    /// Set whether blinking starts with the display phase turned on or off.
+    pub async fn set_blink_phase(&mut self, phase: BlinkPhase) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::BLINK_START, phase == BlinkPhase::On)
+            .await
+    }
+
+    /// Enable or disable synchronizing blinking on the rising edge of the LD/CS pin.
+    pub async fn set_blink_sync(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::SYNC, enable).await
+    }
+
+    /// Tests whether external resistor Rset is open.
+    /// Returns true if Rset is detected as open, false otherwise.
+    pub async fn rset_test_open(&mut self) -> Result<bool, AS1115Error<E>> {
+        Ok((self.read_register(register::DISPLAY_TEST_MODE).await?
+            & register::display_test_mode::RSET_OPEN)
+            != 0)
+    }
+
+    /// Tests whether external resistor Rset is shorted.
+    /// Returns true if Rset is detected as shorted, false otherwise.
+    pub async fn rset_test_short(&mut self) -> Result<bool, AS1115Error<E>> {
+        Ok((self.read_register(register::DISPLAY_TEST_MODE).await?
+            & register::display_test_mode::RSET_SHORT)
+            != 0)
+    }
+
+    /// Start an open or shorted LED test without waiting for it to finish.
+    /// Use `led_test_running` to poll for completion and `read_led_faults` to read the results.
+    pub async fn start_led_test(&mut self, test: LedTest) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::DISPLAY_TEST_MODE,
+            State::<NUM_DIGITS>::led_test_mode(test),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns true while an open or shorted LED test is still in progress.
+    pub async fn led_test_running(&mut self) -> Result<bool, AS1115Error<E>> {
+        Ok((self.read_register(register::DISPLAY_TEST_MODE).await?
+            & register::display_test_mode::LED_TEST)
+            != 0)
+    }
+
+    /// Read the per-segment fault map of the last open or shorted LED test from the diagnostic registers.
+    pub async fn read_led_faults(&mut self) -> Result<LedFaults, AS1115Error<E>> {
+        let mut faults = LedFaults::default();
+        for i in 0..NUM_DIGITS {
+            faults.digits[i as usize] = self.read_register(register::DIAG_DIGIT_0 + i).await?;
+        }
+        Ok(faults)
+    }
+
+    /// Run an open or shorted LED test and wait for it to finish.
+    /// The test status is checked every millisecond using the given delay.
+    /// Returns the per-segment fault map, which is empty if the test detected no errors.
+    /// Returns Timeout if the test does not finish within timeout_ms milliseconds.
+    pub async fn run_led_test<D: DelayNs>(
+        &mut self,
+        test: LedTest,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<LedFaults, AS1115Error<E>> {
+        self.start_led_test(test).await?;
+
+        let mut waited_ms = 0;
+        let test_mode = loop {
+            let test_mode = self.read_register(register::DISPLAY_TEST_MODE).await?;
+            if test_mode & register::display_test_mode::LED_TEST == 0 {
+                break test_mode;
+            }
+            if waited_ms >= timeout_ms {
+                return Err(AS1115Error::Timeout);
+            }
+            delay.delay_ms(1).await;
+            waited_ms += 1;
+        };
+
+        // skip reading the diagnostic registers if the test detected no errors
+        if test_mode & register::display_test_mode::LED_GLOBAL == 0 {
+            return Ok(LedFaults::default());
+        }
+        self.read_led_faults().await
+    }
+
+    // flushes staged digits right away unless writes are buffered
+    async fn commit(&mut self) -> Result<(), AS1115Error<E>> {
+        if self.state.auto_flush() {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn write_digit_intensity(&mut self, digit: u8) -> Result<(), AS1115Error<E>> {
+        let (register, reg_value) = self.state.digit_intensity_register(digit)?;
+        self.write_register(register, reg_value).await?;
+        Ok(())
+    }
+
+    async fn write_shutdown_mode(
+        &mut self,
+        mode: u8,
+        preserve_feature: bool,
+    ) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::SHUTDOWN_MODE,
+            State::<NUM_DIGITS>::shutdown_mode_value(mode, preserve_feature),
+        )
+        .await?;
+        if !preserve_feature {
+            self.state.feature = 0;
+        }
+        Ok(())
+    }
+
+    async fn update_feature(&mut self, mask: u8, enable: bool) -> Result<(), AS1115Error<E>> {
+        let feature = self.state.feature_with(mask, enable);
+        self.write_register(register::FEATURE, feature).await?;
+        self.state.feature = feature;
+        Ok(())
+    }
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, AS1115Error<E>> {
+        let mut buffer = [0; 1];
+        self.i2c
+            .write_read(self.address, &[register], &mut buffer)
+            .await?;
+        Ok(buffer[0])
+    }
+
+    async fn write_register(&mut self, register: u8, value: u8) -> Result<(), AS1115Error<E>> {
+        self.write_register_to_addr(self.address, register, value)
+            .await?;
+        Ok(())
+    }
+
+    async fn write_registers(&mut self, register: u8, values: &[u8]) -> Result<(), AS1115Error<E>> {
+        if !self.state.burst_writes {
+            for (i, &value) in values.iter().enumerate() {
+                self.write_register(register + i as u8, value).await?;
+            }
+            return Ok(());
+        }
+
+        let mut buffer = [0; MAX_DIGITS as usize + 1];
+        buffer[0] = register;
+        buffer[1..=values.len()].copy_from_slice(values);
+        self.i2c
+            .write(self.address, &buffer[..=values.len()])
+            .await?;
+        Ok(())
+    }
+
+    async fn write_register_to_addr(
+        &mut self,
+        address: u8,
+        register: u8,
+        value: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        self.i2c.write(address, &[register, value]).await?;
+        Ok(())
+    }
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, IRQ> AS1115Async<I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+    IRQ: InputPin,
+{
+    /// Read keyscan data only if the IRQ pin signals that it changed since the last read.
+    /// Returns None without any I2C traffic while the IRQ pin is inactive (high).
+    pub async fn keys_changed(&mut self) -> Result<Option<KeyState>, AS1115Error<E>> {
+        if self.irq.is_high().map_err(|_| AS1115Error::IrqPinError)? {
+            return Ok(None);
+        }
+        Ok(Some(self.read_key_state().await?))
+    }
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, IRQ> AS1115Async<I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+    IRQ: Wait,
+{
+    /// Wait until the IRQ pin signals changed keyscan data, then read it.
+    pub async fn wait_for_keys(&mut self) -> Result<KeyState, AS1115Error<E>> {
+        self.irq
+            .wait_for_low()
+            .await
+            .map_err(|_| AS1115Error::IrqPinError)?;
+        self.read_key_state().await
+    }
+}
@@ -0,0 +1,148 @@
+/// Blink frequency selected with the BLINK_FREQ_SET bit of the feature register.
+/// The periods below apply to the internal oscillator, with an external clock they scale with the clock frequency.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlinkFrequency {
+    /// Blink period of typically 1 second (0.5s on, 0.5s off).
+    #[default]
+    Fast,
+    /// Blink period of typically 2 seconds (1s on, 1s off).
+    Slow,
+}
+
+/// Display phase that blinking starts with, selected with the BLINK_START bit of the feature register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlinkPhase {
+    /// Blinking starts with the display turned off.
+    #[default]
+    Off,
+    /// Blinking starts with the display turned on.
+    On,
+}
+
+/// Font used by the hardware decoder for digits with decoding enabled, selected with the DECODE_SET bit of the feature register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecodeType {
+    /// Code-B font: 0-9, '-', E, H, L, P and blank.
+    #[default]
+    CodeB,
+    /// HEX font: 0-9 and A-F.
+    Hex,
+}
+
+/// Letter glyphs used when displaying ASCII text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LetterCase {
+    /// Upper and lower case letters share one glyph from LETTERS.
+    #[default]
+    Insensitive,
+    /// Letters use UPPERCASE_LETTERS and LOWERCASE_LETTERS, so e.g. b/B, c/C, h/H, i/I, o/O, u/U and g/G look different.
+    /// The lowercase u takes the glyph of v/V, so u and v look the same, unlike with Insensitive where U and v differ.
+    Sensitive,
+}
+
+/// Orientation the display is mounted in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rotation {
+    /// Digits upright, DIGIT registers in the order of the digit map.
+    #[default]
+    Normal,
+    /// Display rotated by 180°: digit order is reversed, segments A/D, B/E and C/F are swapped and the DP moves to the following digit.
+    UpsideDown,
+}
+
+/// System clock source selected with the CLK_EN bit of the feature register.
+/// Driving several AS1115s from a shared external clock keeps their multiplexing and blinking in phase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClockSource {
+    /// Internal oscillator.
+    #[default]
+    Internal,
+    /// External clock on the CLK pin of the serial interface.
+    External,
+}
+
+/// Controls when digit updates are written to the AS1115.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Every display call writes the digits it updated right away.
+    #[default]
+    Immediate,
+    /// Display calls only update the shadow buffer, changed digits are written on `flush`.
+    Buffered,
+}
+
+/// Position of a formatted number within the display.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    #[default]
+    Right,
+    /// Centered, with the extra digit on the right if the free space is odd.
+    Center,
+}
+
+/// Position of the minus sign of a negative formatted number.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignPlacement {
+    /// Directly in front of the number, or in front of the zero padding.
+    #[default]
+    Adjacent,
+    /// On the leftmost digit of the display, apart from the zero padding and fill of the number.
+    Edge,
+}
+
+/// Layout options accepted by the `_with` variants of the numeric display functions.
+/// The default right-aligns the number and blanks the other digits, like the plain display functions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub(crate) alignment: Alignment,
+    pub(crate) zero_pad: bool,
+    pub(crate) fill: u8,
+    pub(crate) sign: SignPlacement,
+    pub(crate) min_width: u8,
+}
+
+impl FormatOptions {
+    /// Create options with the default layout.
+    pub const fn new() -> Self {
+        Self {
+            alignment: Alignment::Right,
+            zero_pad: false,
+            fill: 0,
+            sign: SignPlacement::Adjacent,
+            min_width: 0,
+        }
+    }
+
+    /// Set the position of the number within the display.
+    pub const fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Pad the number with leading zeros up to the minimum width, placed after the sign.
+    pub const fn zero_pad(mut self, zero_pad: bool) -> Self {
+        self.zero_pad = zero_pad;
+        self
+    }
+
+    /// Set the segment data shown on digits not used by the number, blank (0) by default.
+    /// Digits with hardware decoding only support a blank fill.
+    pub const fn fill(mut self, segment_data: u8) -> Self {
+        self.fill = segment_data;
+        self
+    }
+
+    /// Set where the minus sign of negative numbers is placed.
+    pub const fn sign(mut self, sign: SignPlacement) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Set the minimum number of digits used by the number including its sign, padded with zeros or the fill.
+    /// Display functions return InvalidValue if the minimum width exceeds the number of digits.
+    pub const fn min_width(mut self, min_width: u8) -> Self {
+        self.min_width = min_width;
+        self
+    }
+}
@@ -2,6 +2,7 @@ pub const DEFAULT_ADDRESS: u8 = 0x00;
 pub const MAX_DIGITS: u8 = 8;
 pub const MAX_INTENSITY: u8 = 15;
 pub const MINUS_SIGN: u8 = 0x01;
+pub const DEGREE_SIGN: u8 = 0x63;
 pub const NUMBERS: [u8; 16] = [
     0x7E, 0x30, 0x6D, 0x79, 0x33, 0x5B, 0x5F, 0x70, 0x7F, 0x7B, 0x77, 0x1F, 0x4E, 0x3D, 0x4F, 0x47,
 ];
@@ -9,6 +10,17 @@ pub const LETTERS: [u8; 26] = [
     0x77, 0x1F, 0x4E, 0x3D, 0x4F, 0x47, 0x5E, 0x37, 0x30, 0x3C, 0x2F, 0x0E, 0x54, 0x15, 0x1D, 0x67,
     0x73, 0x05, 0x5B, 0x0F, 0x3E, 0x1C, 0x2A, 0x49, 0x3B, 0x25,
 ];
+// LETTERS with B and O drawn as 8 and 0 to tell them apart from b and o
+pub const UPPERCASE_LETTERS: [u8; 26] = [
+    0x77, 0x7F, 0x4E, 0x3D, 0x4F, 0x47, 0x5E, 0x37, 0x30, 0x3C, 0x2F, 0x0E, 0x54, 0x15, 0x7E, 0x67,
+    0x73, 0x05, 0x5B, 0x0F, 0x3E, 0x1C, 0x2A, 0x49, 0x3B, 0x25,
+];
+// LETTERS with c, g, h, i and u drawn in lowercase to tell them apart from C, G, H, I and U.
+// The lowercase u shares its glyph (0x1C) with v and V, so u and v can't be told apart in this table.
+pub const LOWERCASE_LETTERS: [u8; 26] = [
+    0x77, 0x1F, 0x0D, 0x3D, 0x4F, 0x47, 0x7B, 0x17, 0x10, 0x3C, 0x2F, 0x0E, 0x54, 0x15, 0x1D, 0x67,
+    0x73, 0x05, 0x5B, 0x0F, 0x1C, 0x1C, 0x2A, 0x49, 0x3B, 0x25,
+];
 
 pub mod segments {
     pub const DP: u8 = 0x80; // Decimal point
@@ -21,6 +33,15 @@ pub mod segments {
     pub const G: u8 = 0x01; // Segment G (middle)
 }
 
+pub mod code_b {
+    pub const MINUS: u8 = 0x0A; // '-'
+    pub const E: u8 = 0x0B;
+    pub const H: u8 = 0x0C;
+    pub const L: u8 = 0x0D;
+    pub const P: u8 = 0x0E;
+    pub const BLANK: u8 = 0x0F;
+}
+
 pub mod register {
     pub const DIGIT_OFFSET: u8 = 0x01; // Digit0 - Digit7
     pub const DECODE_MODE: u8 = 0x09;
@@ -0,0 +1,35 @@
+use crate::MAX_DIGITS;
+
+/// Type of LED test run by the AS1115 diagnostics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedTest {
+    /// Detect open (disconnected) LED segments.
+    Open,
+    /// Detect shorted LED segments.
+    Short,
+}
+
+/// Per-digit, per-segment fault map read from the DIAG_DIGIT registers, indexed by DIGIT register.
+/// Each digit's byte holds the AS1115's physical segment lines in the bit layout of the `segments` constants.
+/// With a `SegmentMap` other than the identity, the bits are the lines the logical segments are wired to, not the logical segments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LedFaults {
+    pub(crate) digits: [u8; MAX_DIGITS as usize],
+}
+
+impl LedFaults {
+    /// Returns the faulty segments of the given digit, or 0 if the digit index is out of bounds.
+    pub fn digit(&self, digit: u8) -> u8 {
+        self.digits.get(digit as usize).copied().unwrap_or(0)
+    }
+
+    /// Returns true if any of the given segments of the digit were flagged as faulty.
+    pub fn is_faulty(&self, digit: u8, segments: u8) -> bool {
+        self.digit(digit) & segments != 0
+    }
+
+    /// Returns true if any segment of any digit was flagged as faulty.
+    pub fn has_faults(&self) -> bool {
+        self.digits.iter().any(|&d| d != 0)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{ascii_to_segment_cased, LetterCase};
+
+/// Character to seven-segment glyph mapping used when displaying text.
+/// Glyphs use the bit layout of the `segments` constants.
+pub trait Font: Sync {
+    /// Returns the segment data for the character, or None if the font has no glyph for it.
+    fn glyph(&self, c: char) -> Option<u8>;
+}
+
+/// Built-in font covering printable ASCII and the degree sign, see `ascii_to_segment_cased`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AsciiFont(pub LetterCase);
+
+/// Built-in font with upper and lower case letters sharing one glyph, used by default.
+pub static ASCII_FONT: AsciiFont = AsciiFont(LetterCase::Insensitive);
+
+/// Built-in font with distinct upper and lower case letter glyphs.
+pub static ASCII_CASED_FONT: AsciiFont = AsciiFont(LetterCase::Sensitive);
+
+impl Font for AsciiFont {
+    fn glyph(&self, c: char) -> Option<u8> {
+        u8::try_from(c)
+            .ok()
+            .and_then(|c| ascii_to_segment_cased(c, self.0))
+    }
+}
+
+/// Font built from a table of characters and their segment data, which can be created in a const context.
+/// Characters not in the table are looked up in the fallback font, if any.
+#[derive(Clone, Copy)]
+pub struct GlyphTable<const N: usize> {
+    glyphs: [(char, u8); N],
+    fallback: Option<&'static dyn Font>,
+}
+
+impl<const N: usize> GlyphTable<N> {
+    /// Create a font from the given characters and their segment data, without a fallback font.
+    pub const fn new(glyphs: [(char, u8); N]) -> Self {
+        Self {
+            glyphs,
+            fallback: None,
+        }
+    }
+
+    /// Use the given font for characters not in the table, e.g. `&ASCII_FONT` to only override or add a few glyphs.
+    pub const fn with_fallback(mut self, fallback: &'static dyn Font) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+}
+
+impl<const N: usize> Font for GlyphTable<N> {
+    fn glyph(&self, c: char) -> Option<u8> {
+        match self.glyphs.iter().find(|(glyph_char, _)| *glyph_char == c) {
+            Some(&(_, segment_data)) => Some(segment_data),
+            None => self.fallback.and_then(|font| font.glyph(c)),
+        }
+    }
+}
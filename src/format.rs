@@ -0,0 +1,341 @@
+use crate::{segments, AS1115Error, Alignment, FormatOptions, SignPlacement};
+use num_traits::ToPrimitive;
+
+// Numeric glyphs that can be sent either as segment data or as hardware decoder codes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Glyph {
+    Numeral(u8),
+    Minus,
+    Blank,
+    Segments(u8), // raw segment data, only blank on decoded digits
+}
+
+// Run of digit positions the formatting functions stage into, left to right.
+// Implemented by a single chip's state and by displays spanning several chips.
+pub(crate) trait DigitSink {
+    fn num_digits(&self) -> u8;
+
+    fn stage_segments(&mut self, position: u8, data: u8);
+
+    fn char_segments(&self, c: char) -> Option<u8>;
+
+    // segment data or decoder code for the glyph at position, InvalidValue if the digit can't show it
+    fn glyph_data<E>(&self, position: u8, glyph: Glyph, dp: bool) -> Result<u8, AS1115Error<E>>;
+
+    // blanks the digit, using the blank decoder code on Code-B digits
+    fn stage_blank(&mut self, position: u8) {
+        let data = self
+            .glyph_data::<()>(position, Glyph::Blank, false)
+            .unwrap_or(0);
+        self.stage_segments(position, data);
+    }
+}
+
+// base^exp, saturating at u64::MAX
+pub(crate) const fn const_pow(base: u64, exp: u32) -> u64 {
+    let mut result: u64 = 1;
+    let mut i = 0;
+    while i < exp {
+        result = result.saturating_mul(base);
+        i += 1;
+    }
+    result
+}
+
+pub(crate) fn clear<D: DigitSink>(sink: &mut D) {
+    for i in 0..sink.num_digits() {
+        sink.stage_blank(i);
+    }
+}
+
+pub(crate) fn ascii<D: DigitSink>(sink: &mut D, chars: &[u8]) {
+    let mut index = 0;
+    let mut i = 0;
+
+    while i < chars.len() && index < sink.num_digits() {
+        let c = chars[i];
+        let segment_data = sink.char_segments(c as char);
+
+        if segment_data.is_none() {
+            i += 1;
+            continue;
+        }
+
+        let mut segment_data = segment_data.unwrap();
+        if i + 1 < chars.len() && chars[i + 1] == b'.' {
+            segment_data |= segments::DP;
+            i += 1;
+        }
+
+        sink.stage_segments(index, segment_data);
+        index += 1;
+        i += 1;
+    }
+}
+
+// Stages chars with the first character at the given position, which may be negative or past the end.
+// Digits not covered by the text are blanked, so stepping the offset scrolls the text across the display.
+pub(crate) fn ascii_at<D: DigitSink>(sink: &mut D, chars: &[u8], offset: i16) {
+    let num_digits = sink.num_digits() as i16;
+    let mut position = offset;
+    let mut i = 0;
+
+    for p in 0..num_digits.min(offset.max(0)) {
+        sink.stage_blank(p as u8);
+    }
+
+    while i < chars.len() && position < num_digits {
+        let mut segment_data = match sink.char_segments(chars[i] as char) {
+            Some(data) => data,
+            None => {
+                i += 1;
+                continue;
+            }
+        };
+        if i + 1 < chars.len() && chars[i + 1] == b'.' {
+            segment_data |= segments::DP;
+            i += 1;
+        }
+
+        if position >= 0 {
+            sink.stage_segments(position as u8, segment_data);
+        }
+        position += 1;
+        i += 1;
+    }
+
+    for p in position.max(0)..num_digits {
+        sink.stage_blank(p as u8);
+    }
+}
+
+// Lays out a number within the display according to options, given its sign and body_len body glyphs.
+// body yields the body glyphs and their DP right to left.
+// Every glyph is checked before any digit is staged, so an error leaves the display unchanged.
+fn place<D: DigitSink, E, B: FnMut() -> (Glyph, bool) + Clone>(
+    sink: &mut D,
+    options: &FormatOptions,
+    is_negative: bool,
+    body_len: u32,
+    body: B,
+) -> Result<(), AS1115Error<E>> {
+    let num_digits = sink.num_digits() as u32;
+    // an edge sign takes digit 0 outside the field, but still counts towards the minimum width
+    let edge_sign = (is_negative && options.sign == SignPlacement::Edge) as u32;
+    let content_len = (is_negative as u32 - edge_sign) + body_len;
+    let field_len = content_len.max((options.min_width as u32).saturating_sub(edge_sign));
+    if edge_sign + field_len > num_digits {
+        return Err(AS1115Error::InvalidValue);
+    }
+
+    let free = num_digits - edge_sign - field_len;
+    let field_start = edge_sign
+        + match options.alignment {
+            Alignment::Left => 0,
+            Alignment::Right => free,
+            Alignment::Center => free / 2,
+        };
+    let field_end = field_start + field_len;
+    let body_start = field_end - body_len;
+    let sign_position = match options.sign {
+        SignPlacement::Edge => 0,
+        SignPlacement::Adjacent if options.zero_pad => field_start,
+        SignPlacement::Adjacent => body_start.saturating_sub(1),
+    };
+    let fill = match options.fill {
+        0 => Glyph::Blank,
+        segment_data => Glyph::Segments(segment_data),
+    };
+
+    let glyphs = |mut body: B| {
+        (0..num_digits).rev().map(move |position| {
+            let (glyph, dp) = if (body_start..field_end).contains(&position) {
+                body()
+            } else if is_negative && position == sign_position {
+                (Glyph::Minus, false)
+            } else if options.zero_pad && (field_start..field_end).contains(&position) {
+                (Glyph::Numeral(0), false)
+            } else {
+                (fill, false)
+            };
+            (position as u8, glyph, dp)
+        })
+    };
+    for (position, glyph, dp) in glyphs(body.clone()) {
+        sink.glyph_data::<E>(position, glyph, dp)?;
+    }
+    for (position, glyph, dp) in glyphs(body) {
+        let data = sink.glyph_data::<E>(position, glyph, dp)?;
+        sink.stage_segments(position, data);
+    }
+
+    Ok(())
+}
+
+// Splits an integer value into its sign and magnitude, covering the full i64 and u64 ranges
+fn sign_magnitude<E, T: ToPrimitive>(value: T) -> Result<(bool, u64), AS1115Error<E>> {
+    match value.to_i64() {
+        Some(value) => Ok((value < 0, value.unsigned_abs())),
+        None => value
+            .to_u64()
+            .map(|value| (false, value))
+            .ok_or(AS1115Error::InvalidValue),
+    }
+}
+
+// Formats value in the given base from 2 to 16, with a minus sign for negative values
+pub(crate) fn integer<D: DigitSink, E, T: ToPrimitive>(
+    sink: &mut D,
+    value: T,
+    base: u32,
+    options: &FormatOptions,
+) -> Result<(), AS1115Error<E>> {
+    if !(2..=16).contains(&base) {
+        return Err(AS1115Error::InvalidValue);
+    }
+    let (is_negative, mut num) = sign_magnitude(value)?;
+    let num_digits = sink.num_digits();
+
+    // Check if value will fit, a negative value needs one digit for the minus sign.
+    // A saturated power is beyond u64, so any magnitude fits.
+    let available = if is_negative {
+        num_digits - 1
+    } else {
+        num_digits
+    };
+    let limit = const_pow(base as u64, available as u32);
+    if limit != u64::MAX && num >= limit {
+        return Err(AS1115Error::InvalidValue);
+    }
+
+    let mut body_len = 1;
+    let mut rest = num / base as u64;
+    while rest > 0 {
+        body_len += 1;
+        rest /= base as u64;
+    }
+
+    place(sink, options, is_negative, body_len, move || {
+        let glyph = Glyph::Numeral((num % base as u64) as u8);
+        num /= base as u64;
+        (glyph, false)
+    })
+}
+
+// Formats value / 10^decimals with the DP after the units digit, using only integer arithmetic.
+// Decimals that don't fit are dropped, rounding half away from zero.
+pub(crate) fn fixed<D: DigitSink, E>(
+    sink: &mut D,
+    value: i32,
+    decimals: u8,
+    options: &FormatOptions,
+) -> Result<(), AS1115Error<E>> {
+    let num_digits = sink.num_digits() as u32;
+    let is_negative = value < 0;
+
+    // keeps the math in u64, an i32 scaled by more than 18 decimals rounds to 0 at any supported width
+    let (value, decimals) = if decimals > 18 {
+        (0, 18)
+    } else {
+        (value.unsigned_abs() as u64, decimals as u32)
+    };
+
+    // drop as few decimals as needed for the value to fit
+    let mut dropped = 0;
+    let (mut num, decimals, show_minus) = loop {
+        let rounded = if dropped == 0 {
+            value
+        } else {
+            (value + 5 * 10u64.pow(dropped - 1)) / 10u64.pow(dropped)
+        };
+        let shown_decimals = decimals - dropped;
+        let integer_digits = count_digits(rounded / 10u64.pow(shown_decimals));
+        let show_minus = is_negative && rounded != 0;
+        if show_minus as u32 + integer_digits + shown_decimals <= num_digits {
+            break (rounded, shown_decimals, show_minus);
+        }
+        if dropped == decimals {
+            return Err(AS1115Error::InvalidValue);
+        }
+        dropped += 1;
+    };
+
+    let body_len = count_digits(num).max(decimals + 1);
+    let mut place_index = 0;
+    place(sink, options, show_minus, body_len, move || {
+        let dp = decimals > 0 && place_index == decimals;
+        let glyph = Glyph::Numeral((num % 10) as u8);
+        num /= 10;
+        place_index += 1;
+        (glyph, dp)
+    })
+}
+
+// number of decimal digits needed to show value, at least 1
+fn count_digits(mut value: u64) -> u32 {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(feature = "display_float_value")]
+pub(crate) fn float<D: DigitSink, E>(
+    sink: &mut D,
+    float_val: f32,
+    precision: u8,
+    options: &FormatOptions,
+) -> Result<(), AS1115Error<E>> {
+    let num_digits = sink.num_digits();
+
+    if precision < 1 || precision >= num_digits || !float_val.is_finite() {
+        return Err(AS1115Error::InvalidValue);
+    }
+
+    let is_negative = float_val.is_sign_negative();
+    let abs_val = float_val.abs();
+
+    // Check value bounds
+    let integer_digits = if abs_val < 1.0 {
+        1
+    } else {
+        let mut temp = abs_val as u32;
+        let mut digits = 0;
+        while temp > 0 {
+            digits += 1;
+            temp /= 10;
+        }
+        digits
+    };
+    let total_digits = (if is_negative { 1 } else { 0 }) + integer_digits + precision;
+    if total_digits > num_digits {
+        return Err(AS1115Error::InvalidValue);
+    }
+
+    // Scale number to integer value for formatting
+    let mut scale_factor = 1.0f32;
+    for _ in 0..precision {
+        scale_factor *= 10.0;
+    }
+    let rounded_val = abs_val * scale_factor + 0.5;
+    let mut digits = rounded_val as u32;
+
+    // possible for rounding to cause overflow, caught by the layout
+    let body_len = count_digits(digits as u64).max(precision as u32 + 1);
+    let mut place_index = 0;
+    place(sink, options, is_negative, body_len, move || {
+        let dp = place_index == precision;
+        let glyph = Glyph::Numeral((digits % 10) as u8);
+        digits /= 10;
+        place_index += 1;
+        (glyph, dp)
+    })
+}
+
+pub(crate) fn segment_data<D: DigitSink>(sink: &mut D, segments: &[u8]) {
+    for (index, &segment) in segments.iter().take(sink.num_digits() as usize).enumerate() {
+        sink.stage_segments(index as u8, segment);
+    }
+}
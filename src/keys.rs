@@ -0,0 +1,259 @@
+/// Number of keys supported by the AS1115 keyscan (8 on KEYA and 8 on KEYB).
+pub const NUM_KEYS: u8 = 16;
+
+// KEYA bits shared with the SEGF and SEGG self-addressing pins
+const SELF_ADDRESSING_KEYS: u16 = 0x0300;
+
+/// Placeholder IRQ pin type for drivers created without an IRQ pin.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoIrq;
+
+/// Keyscan line a key is connected to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyLine {
+    KeyA,
+    KeyB,
+}
+
+/// Logical key index 0-15, matching the bit position in `KeyState` and `read_keys` (KEYB keys 0-7, KEYA keys 8-15).
+/// Within a line, keys use the bit order of the `segments` constants, e.g. the key on SEGG is bit 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key(u8);
+
+impl Key {
+    /// Create a key from its logical index.
+    /// Returns None if the index is not below NUM_KEYS.
+    pub const fn new(index: u8) -> Option<Self> {
+        if index < NUM_KEYS {
+            Some(Key(index))
+        } else {
+            None
+        }
+    }
+
+    /// Create a key from the keyscan line and the segment line (one of the `segments` constants) it is connected to.
+    /// Returns None if segment is not a single segment line.
+    pub const fn from_line(line: KeyLine, segment: u8) -> Option<Self> {
+        if !segment.is_power_of_two() {
+            return None;
+        }
+        let bit = segment.trailing_zeros() as u8;
+        match line {
+            KeyLine::KeyA => Some(Key(bit + 8)),
+            KeyLine::KeyB => Some(Key(bit)),
+        }
+    }
+
+    /// Returns the logical index of the key.
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the keyscan line the key is connected to.
+    pub const fn line(self) -> KeyLine {
+        if self.0 >= 8 {
+            KeyLine::KeyA
+        } else {
+            KeyLine::KeyB
+        }
+    }
+
+    /// Returns the segment line the key is connected to, as one of the `segments` constants.
+    pub const fn segment(self) -> u8 {
+        1 << (self.0 % 8)
+    }
+}
+
+/// Set of pressed keys read from the KEYA and KEYB registers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeyState(u16);
+
+impl KeyState {
+    /// Create a key state from a bitmap of pressed keys indexed by `Key::index`.
+    pub const fn from_bits(bits: u16) -> Self {
+        KeyState(bits)
+    }
+
+    /// Create a key state from raw KEYA and KEYB register values, which read 0 for pressed keys.
+    /// If self_addressing is true, the KEYA keys shared with the self-addressing pins are never reported as pressed.
+    pub const fn from_registers(key_a: u8, key_b: u8, self_addressing: bool) -> Self {
+        let mut bits = !((key_a as u16) << 8 | key_b as u16);
+        if self_addressing {
+            bits &= !SELF_ADDRESSING_KEYS;
+        }
+        KeyState(bits)
+    }
+
+    /// Returns the bitmap of pressed keys indexed by `Key::index`.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns true if no key is pressed.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if the given key is pressed.
+    pub const fn is_pressed(self, key: Key) -> bool {
+        self.0 & (1 << key.0) != 0
+    }
+
+    /// Returns an iterator over the pressed keys in index order.
+    pub fn iter(self) -> KeyStateIter {
+        KeyStateIter(self.0)
+    }
+
+    /// Returns the keys that were pressed and released going from the previous state to this one.
+    pub const fn diff(self, previous: &KeyState) -> KeyDiff {
+        KeyDiff {
+            pressed: KeyState(self.0 & !previous.0),
+            released: KeyState(previous.0 & !self.0),
+        }
+    }
+}
+
+impl IntoIterator for KeyState {
+    type Item = Key;
+    type IntoIter = KeyStateIter;
+
+    fn into_iter(self) -> KeyStateIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the pressed keys of a `KeyState`.
+#[derive(Clone, Debug)]
+pub struct KeyStateIter(u16);
+
+impl Iterator for KeyStateIter {
+    type Item = Key;
+
+    fn next(&mut self) -> Option<Key> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(Key(index))
+    }
+}
+
+/// Keys that changed between two `KeyState`s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeyDiff {
+    pub pressed: KeyState,
+    pub released: KeyState,
+}
+
+/// Key event produced by `KeyManager`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyEvent {
+    /// Key was pressed.
+    Pressed(Key),
+    /// Key was released.
+    Released(Key),
+    /// Key has been held down for the hold time, with the number of ticks since it was pressed.
+    Held(Key, u32),
+    /// Key is still held down after the hold time and the repeat interval has elapsed.
+    Repeat(Key),
+}
+
+/// Events produced by a single `KeyManager::update` call, at most one per key.
+#[derive(Clone, Debug)]
+pub struct KeyEvents {
+    events: [Option<KeyEvent>; NUM_KEYS as usize],
+    len: usize,
+    index: usize,
+}
+
+impl KeyEvents {
+    fn push(&mut self, event: KeyEvent) {
+        self.events[self.len] = Some(event);
+        self.len += 1;
+    }
+}
+
+impl Iterator for KeyEvents {
+    type Item = KeyEvent;
+
+    fn next(&mut self) -> Option<KeyEvent> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.index += 1;
+        self.events[self.index - 1]
+    }
+}
+
+/// Tracks keyscan state between reads and turns it into press, release, hold and repeat events.
+/// Timing uses caller-provided ticks in any unit (e.g. milliseconds), wrapping around is supported.
+pub struct KeyManager {
+    pressed: KeyState,
+    held: u16,
+    hold_ticks: u32,
+    repeat_ticks: u32,
+    pressed_at: [u32; NUM_KEYS as usize],
+    last_event_at: [u32; NUM_KEYS as usize],
+}
+
+impl KeyManager {
+    /// Create a new key manager reporting Held after hold_ticks and then Repeat every repeat_ticks (0 disables repeating).
+    pub fn new(hold_ticks: u32, repeat_ticks: u32) -> Self {
+        Self {
+            pressed: KeyState::default(),
+            held: 0,
+            hold_ticks,
+            repeat_ticks,
+            pressed_at: [0; NUM_KEYS as usize],
+            last_event_at: [0; NUM_KEYS as usize],
+        }
+    }
+
+    /// Returns the keys currently considered pressed.
+    pub fn pressed(&self) -> KeyState {
+        self.pressed
+    }
+
+    /// Update the key state with a new value from `read_key_state` taken at the given tick and return the resulting events.
+    pub fn update(&mut self, keys: KeyState, now: u32) -> KeyEvents {
+        let mut events = KeyEvents {
+            events: [None; NUM_KEYS as usize],
+            len: 0,
+            index: 0,
+        };
+
+        for key in (0..NUM_KEYS).map(Key) {
+            let i = key.index() as usize;
+            let mask = 1 << key.index();
+            let is_pressed = keys.is_pressed(key);
+            let was_pressed = self.pressed.is_pressed(key);
+
+            if is_pressed && !was_pressed {
+                self.pressed_at[i] = now;
+                self.last_event_at[i] = now;
+                events.push(KeyEvent::Pressed(key));
+            } else if !is_pressed && was_pressed {
+                self.held &= !mask;
+                events.push(KeyEvent::Released(key));
+            } else if was_pressed {
+                let duration = now.wrapping_sub(self.pressed_at[i]);
+                if self.held & mask == 0 {
+                    if duration >= self.hold_ticks {
+                        self.held |= mask;
+                        self.last_event_at[i] = now;
+                        events.push(KeyEvent::Held(key, duration));
+                    }
+                } else if self.repeat_ticks > 0
+                    && now.wrapping_sub(self.last_event_at[i]) >= self.repeat_ticks
+                {
+                    self.last_event_at[i] = now;
+                    events.push(KeyEvent::Repeat(key));
+                }
+            }
+        }
+
+        self.pressed = keys;
+        events
+    }
+}
@@ -0,0 +1,128 @@
+use crate::segments;
+
+/// Mapping from the logical segments used by the driver (the `segments` constants) to the segment lines of the AS1115 they are wired to.
+/// Applied to the data of every digit without hardware decoding when it is written to the AS1115. LED faults report physical segment lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SegmentMap([u8; 8]); // physical segment for each logical bit, G (bit 0) to DP (bit 7)
+
+impl SegmentMap {
+    /// Segments wired in the AS1115's own bit order.
+    pub const IDENTITY: SegmentMap = SegmentMap([
+        segments::G,
+        segments::F,
+        segments::E,
+        segments::D,
+        segments::C,
+        segments::B,
+        segments::A,
+        segments::DP,
+    ]);
+
+    /// Create a map from the segment lines driving the logical segments DP, A, B, C, D, E, F and G, in that order.
+    /// Returns None if the wiring is not a permutation of the `segments` constants.
+    pub const fn from_wiring(wiring: [u8; 8]) -> Option<Self> {
+        let mut map = [0; 8];
+        let mut seen = 0;
+        let mut i = 0;
+        while i < 8 {
+            let line = wiring[i];
+            if !line.is_power_of_two() || seen & line != 0 {
+                return None;
+            }
+            seen |= line;
+            map[7 - i] = line;
+            i += 1;
+        }
+        Some(SegmentMap(map))
+    }
+
+    /// Returns the map with the two given segment lines swapped, e.g. `SegmentMap::IDENTITY.swap(segments::B, segments::F)`.
+    /// Values that are not single `segments` constants leave the map unchanged.
+    pub const fn swap(self, a: u8, b: u8) -> Self {
+        let mut map = self.0;
+        let mut i = 0;
+        while i < 8 {
+            if map[i] == a {
+                map[i] = b;
+            } else if map[i] == b {
+                map[i] = a;
+            }
+            i += 1;
+        }
+        SegmentMap(map)
+    }
+
+    /// Returns the segment line data for the given logical segment data.
+    pub const fn apply(&self, data: u8) -> u8 {
+        let mut result = 0;
+        let mut bit = 0;
+        while bit < 8 {
+            if data & (1 << bit) != 0 {
+                result |= self.0[bit];
+            }
+            bit += 1;
+        }
+        result
+    }
+}
+
+impl Default for SegmentMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Mapping from logical digit positions, counted from the left, to the DIGIT registers of the AS1115 driving them.
+/// Display functions and per-digit setters use logical positions, the decode mask and LED faults use DIGIT registers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DigitMap([u8; 8]); // DIGIT register for each logical position
+
+impl DigitMap {
+    /// Logical position N drives DIGIT register N.
+    pub const IDENTITY: DigitMap = DigitMap([0, 1, 2, 3, 4, 5, 6, 7]);
+
+    /// Create a map from the DIGIT registers driving logical positions 0, 1, 2 and so on, e.g. `DigitMap::new(&[1, 0, 3, 2])`.
+    /// Positions after the given ones drive their own DIGIT register.
+    /// Returns None if the registers are not a permutation of 0 to order.len() - 1.
+    pub const fn new(order: &[u8]) -> Option<Self> {
+        if order.len() > 8 {
+            return None;
+        }
+        let mut map = Self::IDENTITY.0;
+        let mut seen = 0u8;
+        let mut i = 0;
+        while i < order.len() {
+            let digit = order[i];
+            if digit as usize >= order.len() || seen & (1 << digit) != 0 {
+                return None;
+            }
+            seen |= 1 << digit;
+            map[i] = digit;
+            i += 1;
+        }
+        Some(DigitMap(map))
+    }
+
+    /// Digits wired right to left, logical position 0 driving DIGIT register num_digits - 1.
+    pub const fn reversed(num_digits: u8) -> Self {
+        let num_digits = if num_digits > 8 { 8 } else { num_digits };
+        let mut map = Self::IDENTITY.0;
+        let mut i = 0;
+        while i < num_digits {
+            map[i as usize] = num_digits - 1 - i;
+            i += 1;
+        }
+        DigitMap(map)
+    }
+
+    /// Returns the DIGIT register driving the given logical position.
+    pub const fn digit(&self, position: u8) -> u8 {
+        self.0[position as usize & 0x07]
+    }
+}
+
+impl Default for DigitMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
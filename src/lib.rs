@@ -1,28 +1,99 @@
 #![no_std]
 
+#[cfg(feature = "async")]
+mod asynch;
+mod config;
 mod constants;
-
+mod diagnostics;
+mod font;
+mod format;
+mod keys;
+mod layout;
+mod multi;
+mod state;
+mod virtual_display;
+mod writer;
+
+#[cfg(feature = "async")]
+pub use asynch::AS1115Async;
+pub use config::*;
 pub use constants::*;
+pub use diagnostics::*;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
 use embedded_hal::i2c::I2c;
+pub use font::*;
+pub use keys::*;
+pub use layout::*;
+pub use multi::AS1115Multi;
 use num_traits::ToPrimitive;
+use state::{combine_keys, State};
+pub use virtual_display::VirtualDisplay;
+pub use writer::DisplayWriter;
 
 /// Convert an ASCII character to the corresponding seven-segment display encoding.
-/// Supports only alphanumeric characters (0-9, a-z, A-Z).
+/// Supports the printable ASCII range (0x20-0x7E) and the Latin-1 degree sign (0xB0).
+/// Letters share one glyph per case from LETTERS, symbols without a seven-segment shape are approximated as noted below.
 pub fn ascii_to_segment(c: u8) -> Option<u8> {
     match c {
         b'0'..=b'9' => Some(NUMBERS[(c - b'0') as usize]),
         b'a'..=b'z' => Some(LETTERS[(c - b'a') as usize]),
         b'A'..=b'Z' => Some(LETTERS[(c - b'A') as usize]),
         b' ' => Some(0),
+        b'!' => Some(0xB0),  // 1 with DP
+        b'"' => Some(0x22),  // B and F
+        b'#' => Some(0x3F),  // approximated as H with bottom bar
+        b'$' => Some(0x5B),  // same as S
+        b'%' => Some(0xA5),  // slash with DP
+        b'&' => Some(0x31),  // approximation, no seven-segment shape
+        b'\'' => Some(0x20), // B only
+        b'(' => Some(0x4E),  // same as [
+        b')' => Some(0x78),  // same as ]
+        b'*' => Some(0x63),  // same as degree sign
+        b'+' => Some(0x07),  // left half with middle bar
+        b',' => Some(0x04),  // E only
+        b'-' => Some(MINUS_SIGN),
+        b'.' => Some(0x80), // DP only, merged into the previous digit by the display functions
+        b'/' => Some(0x25), // B, G and E
+        b':' => Some(0x48), // top and bottom bars
+        b';' => Some(0x58), // top and bottom bars with C
+        b'<' => Some(0x43), // approximation using A, F and G
+        b'=' => Some(0x09), // middle and bottom bars
+        b'>' => Some(0x61), // approximation using A, B and G
+        b'?' => Some(0xE5), // 2 without bottom bar, with DP
+        b'@' => Some(0x7D), // a with top bar
+        b'[' => Some(0x4E), // same as C
+        b'\\' => Some(0x13), // F, G and C
+        b']' => Some(0x78), // A, B, C and D
+        b'^' => Some(0x62), // A, B and F
+        b'_' => Some(0x08), // D only
+        b'`' => Some(0x02), // F only
+        b'{' => Some(0x4E), // same as [
+        b'|' => Some(0x06), // E and F
+        b'}' => Some(0x78), // same as ]
+        b'~' => Some(0x40), // A only
+        0xB0 => Some(DEGREE_SIGN), // Latin-1 degree sign
         _ => None,
     }
 }
 
+/// Convert an ASCII character to the corresponding seven-segment display encoding using the given letter case mode.
+/// Characters other than letters are mapped the same as `ascii_to_segment`.
+pub fn ascii_to_segment_cased(c: u8, letter_case: LetterCase) -> Option<u8> {
+    match (letter_case, c) {
+        (LetterCase::Sensitive, b'a'..=b'z') => Some(LOWERCASE_LETTERS[(c - b'a') as usize]),
+        (LetterCase::Sensitive, b'A'..=b'Z') => Some(UPPERCASE_LETTERS[(c - b'A') as usize]),
+        _ => ascii_to_segment(c),
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum AS1115Error<E> {
     I2cError(E),
     InvalidValue,
     InvalidLocation(u8),
+    Timeout,
+    IrqPinError,
 }
 
 impl<E> From<E> for AS1115Error<E> {
@@ -31,82 +102,62 @@ impl<E> From<E> for AS1115Error<E> {
     }
 }
 
-pub struct AS1115<I2C, const NUM_DIGITS: u8> {
+pub struct AS1115<I2C, const NUM_DIGITS: u8, IRQ = NoIrq> {
     pub i2c: I2C,
     pub address: u8,
-    intensity: [u8; MAX_DIGITS as usize], // ideally NUM_DIGITS
+    irq: IRQ,
+    state: State<NUM_DIGITS>,
 }
 
 impl<I2C, E, const NUM_DIGITS: u8> AS1115<I2C, NUM_DIGITS>
 where
     I2C: I2c<Error = E>,
 {
-    const NUM_DIGITS_VALID: () = {
-        assert!(
-            NUM_DIGITS >= 1 && NUM_DIGITS <= 8,
-            "NUM_DIGITS must be between 1 and 8"
-        );
-    };
-
-    const fn const_pow(base: u32, exp: u32) -> u32 {
-        let mut result = 1;
-        let mut i = 0;
-        while i < exp {
-            result *= base;
-            i += 1;
-        }
-        result
-    }
-
-    const fn max_unsigned_decimal() -> u32 {
-        Self::const_pow(10, NUM_DIGITS as u32) - 1
-    }
-
-    const fn max_signed_decimal() -> i32 {
-        if NUM_DIGITS == 1 {
-            9 // No space for minus sign in 1-digit display
-        } else {
-            (Self::const_pow(10, (NUM_DIGITS - 1) as u32) - 1) as i32
-        }
-    }
-
-    const fn max_unsigned_hex() -> u32 {
-        Self::const_pow(16, NUM_DIGITS as u32) - 1
-    }
-
-    const fn max_signed_hex() -> i32 {
-        if NUM_DIGITS == 1 {
-            15 // No space for minus sign in 1-digit display
-        } else {
-            (Self::const_pow(16, (NUM_DIGITS - 1) as u32) - 1) as i32
-        }
-    }
-
     /// Create a new AS1115 instance with the given I2C interface.
     pub fn new(i2c: I2C) -> Self {
-        let _ = Self::NUM_DIGITS_VALID;
         Self {
             i2c,
             address: DEFAULT_ADDRESS,
-            intensity: [0; MAX_DIGITS as usize],
+            irq: NoIrq,
+            state: State::new(),
         }
     }
 
     /// Create a new AS1115 instance with the given I2C interface and address (using self-addressing with KEYA, SEGF, and SEGG pins).
     pub fn new_with_addr(i2c: I2C, address: u8) -> Self {
-        let _ = Self::NUM_DIGITS_VALID;
         Self {
             i2c,
             address,
-            intensity: [0; MAX_DIGITS as usize],
+            irq: NoIrq,
+            state: State::new(),
         }
     }
 
+    /// Use the given pin connected to the AS1115's IRQ output to only read keyscan data when it changed.
+    pub fn with_irq<IRQ>(self, irq: IRQ) -> AS1115<I2C, NUM_DIGITS, IRQ> {
+        AS1115 {
+            i2c: self.i2c,
+            address: self.address,
+            irq,
+            state: self.state,
+        }
+    }
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, IRQ> AS1115<I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+{
     /// Destroy the AS1115 instance and return the underlying I2C interface.
     pub fn destroy(self) -> I2C {
         self.i2c
     }
 
+    /// Destroy the AS1115 instance and return the underlying I2C interface and IRQ pin.
+    pub fn destroy_with_irq(self) -> (I2C, IRQ) {
+        (self.i2c, self.irq)
+    }
+
     /// Initialize the AS1115 with the given global intensity.
     pub fn init(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
         self.write_register_to_addr(
@@ -123,137 +174,270 @@ where
             )?;
         }
 
-        self.write_register(register::DECODE_MODE, register::decode_mode::NO_DECODE)?;
+        self.configure(intensity)
+    }
+
+    /// Put the AS1115 into shutdown mode, turning the display off.
+    /// Digit data and intensity settings are kept and shown again on `wake`.
+    /// If preserve_feature is false, the feature register (blink, clock and decode font settings) is reset to defaults.
+    pub fn shutdown(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.write_shutdown_mode(register::shutdown_mode::SHUTDOWN_MODE, preserve_feature)
+    }
+
+    /// Wake the AS1115 from shutdown mode, resuming normal operation.
+    /// If preserve_feature is false, the feature register (blink, clock and decode font settings) is reset to defaults.
+    pub fn wake(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.write_shutdown_mode(register::shutdown_mode::NORMAL_OPERATION, preserve_feature)
+    }
+
+    /// Reset all control registers except the feature register to their power-up defaults.
+    /// If reapply is true, the known configuration (normal operation, decode mode, scan limit, intensities and digit data) is written again afterwards.
+    /// Otherwise the AS1115 is left in its default state, which is shutdown mode.
+    /// A self-addressed AS1115 falls back to DEFAULT_ADDRESS on reset and is re-provisioned with its address as in `init`.
+    pub fn reset_registers(&mut self, reapply: bool) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::FEATURE,
+            self.state.feature | register::feature::REG_RESET,
+        )?;
+
+        if self.address != DEFAULT_ADDRESS {
+            self.write_register_to_addr(
+                DEFAULT_ADDRESS,
+                register::SELF_ADDRESSING,
+                register::self_addressing::USER_SET_ADDR,
+            )?;
+        }
+
+        self.write_register(register::FEATURE, self.state.feature)?;
+
+        if !reapply {
+            // the digit registers no longer match the shadow buffer
+            self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+            return Ok(());
+        }
+
+        self.write_register(
+            register::SHUTDOWN_MODE,
+            register::shutdown_mode::NORMAL_OPERATION | register::shutdown_mode::PRESERVE_FEATURE,
+        )?;
+        self.write_register(register::DECODE_MODE, self.state.decode_mask)?;
         self.write_register(register::SCAN_LIMIT, NUM_DIGITS - 1)?;
-        self.set_intensity(intensity)?;
+        self.write_register(register::GLOBAL_INTENSITY, self.state.intensity[0])?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i)?;
+        }
+        let digits = self.state.register_data();
+        self.write_registers(register::DIGIT_OFFSET, &digits[..NUM_DIGITS as usize])?;
+        self.state.dirty = 0;
+        Ok(())
+    }
 
+    /// Set whether digit updates are written to the AS1115 immediately or buffered until `flush`.
+    /// In buffered mode only digits whose data changed are written on flush.
+    pub fn set_write_mode(&mut self, write_mode: WriteMode) {
+        self.state.write_mode = write_mode;
+    }
+
+    /// Enable or disable burst writes, which update consecutive digit registers in a single I2C transaction using the AS1115's register auto-increment.
+    /// Enabled by default, when disabled every digit register is written in its own transaction.
+    pub fn set_burst_writes(&mut self, enable: bool) {
+        self.state.burst_writes = enable;
+    }
+
+    /// Set whether ASCII text is displayed with distinct upper and lower case letter glyphs.
+    /// Applies to text displayed afterwards with the built-in ASCII font, a custom font set with `set_font` is left in place.
+    pub fn set_letter_case(&mut self, letter_case: LetterCase) {
+        self.state.set_letter_case(letter_case);
+    }
+
+    /// Set the font used to display text, replacing the built-in ASCII font and its letter case mode.
+    /// Applies to text displayed afterwards.
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        self.state.font = font;
+    }
+
+    /// Set the segment wiring of the board, applied to every digit without hardware decoding.
+    /// Rewrites all digits with the new wiring.
+    pub fn set_segment_map(&mut self, segment_map: SegmentMap) -> Result<(), AS1115Error<E>> {
+        self.state.segment_map = segment_map;
+        self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+        self.commit()
+    }
+
+    /// Set which DIGIT register drives each logical digit position, e.g. `DigitMap::reversed(NUM_DIGITS)` for modules wired right to left.
+    /// Moves the current digit data and intensities to their new registers.
+    /// Returns InvalidValue if a position below NUM_DIGITS maps to a register beyond NUM_DIGITS.
+    pub fn set_digit_map(&mut self, digit_map: DigitMap) -> Result<(), AS1115Error<E>> {
+        self.state.set_layout(digit_map, self.state.rotation)?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i)?;
+        }
+        self.commit()
+    }
+
+    /// Set the orientation the display is mounted in, applied to every digit write on top of the digit map.
+    /// Returns InvalidValue when turning the display upside down while hardware decoding is enabled, as the decoder font can't be rotated.
+    pub fn set_rotation(&mut self, rotation: Rotation) -> Result<(), AS1115Error<E>> {
+        self.state.set_layout(self.state.digit_map, rotation)?;
+        for i in (0..NUM_DIGITS).step_by(2) {
+            self.write_digit_intensity(i)?;
+        }
+        self.commit()
+    }
+
+    /// Write all changed digits from the shadow buffer to the AS1115.
+    pub fn flush(&mut self) -> Result<(), AS1115Error<E>> {
+        // write each run of consecutive dirty digits together
+        let mut next = 0;
+        while let Some((start, end)) = self.state.next_dirty_run(next) {
+            let digits = self.state.register_data();
+            self.write_registers(
+                register::DIGIT_OFFSET + start,
+                &digits[start as usize..end as usize],
+            )?;
+            self.state.mark_clean(start, end);
+            next = end;
+        }
         Ok(())
     }
 
     /// Clear all digit data.
     pub fn clear(&mut self) -> Result<(), AS1115Error<E>> {
-        for i in 0..NUM_DIGITS {
-            self.set_digit_segment_data(i, 0)?;
-        }
-        Ok(())
+        self.state.clear();
+        self.commit()
     }
 
     /// Display best-effort ASCII characters on the seven-segment display.
     /// Skips over any characters that do not have a valid segment mapping.
     /// Decimal points are included using the seven-segment display's DP segment.
     /// Truncates the input to fit NUM_DIGITS.
+    /// Text is sent as segment data, which digits with hardware decoding show as decoder glyphs instead, so keep text on digits without decoding.
     pub fn display_ascii(&mut self, chars: &[u8]) -> Result<(), AS1115Error<E>> {
-        let mut index = 0;
-        let mut i = 0;
-
-        while i < chars.len() && index < NUM_DIGITS {
-            let c = chars[i];
-            let segment_data = ascii_to_segment(c);
-
-            if segment_data.is_none() {
-                i += 1;
-                continue;
-            }
-
-            let mut segment_data = segment_data.unwrap();
-            if i + 1 < chars.len() && chars[i + 1] == b'.' {
-                segment_data |= segments::DP;
-                i += 1;
-            }
+        self.state.display_ascii(chars);
+        self.commit()
+    }
 
-            self.set_digit_segment_data(index, segment_data)?;
-            index += 1;
-            i += 1;
-        }
-        Ok(())
+    /// Returns a writer for formatted text, e.g. `write!(display.writer(), "{:>4}", x)`.
+    /// Digits are written when the writer is finished or dropped, see `DisplayWriter`.
+    pub fn writer(&mut self) -> DisplayWriter<'_, I2C, NUM_DIGITS, IRQ> {
+        DisplayWriter::new(self)
     }
 
     /// Display an integer value in decimal format on the seven-segment display.
+    /// Accepts any integer type, the full i64 and u64 ranges are supported.
     /// Supports negative numbers by prepending a minus sign.
     /// Returns InvalidValue if the value is too large to fit in the display.
     pub fn display_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
     where
         T: ToPrimitive,
     {
-        let signed_value = value.to_i32().ok_or(AS1115Error::InvalidValue)?;
-
-        // Check if value will fit
-        if signed_value >= 0 {
-            if signed_value as u32 > Self::max_unsigned_decimal() {
-                return Err(AS1115Error::InvalidValue);
-            }
-        } else {
-            if -signed_value > Self::max_signed_decimal() {
-                return Err(AS1115Error::InvalidValue);
-            }
-        }
-
-        let is_negative = signed_value < 0;
-        let mut num = signed_value.unsigned_abs();
-
-        let mut digit_index = NUM_DIGITS;
-
-        while digit_index > 0 && (num > 0 || digit_index == NUM_DIGITS) {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, NUMBERS[(num % 10) as usize])?;
-            num /= 10;
-        }
-
-        if is_negative {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, MINUS_SIGN)?;
-        }
-
-        while digit_index > 0 {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, 0)?;
-        }
+        self.state.display_radix(value, 10, &FormatOptions::new())?;
+        self.commit()
+    }
 
-        Ok(())
+    /// Display an integer value in decimal format laid out according to the given options, see `display_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub fn display_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 10, options)?;
+        self.commit()
     }
 
     /// Display an integer value in hexadecimal format on the seven-segment display.
+    /// Accepts any integer type, the full i64 and u64 ranges are supported.
     /// Supports negative numbers by prepending a minus sign.
     /// Returns InvalidValue if the number is too large to fit in the display.
     pub fn display_hex_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
     where
         T: ToPrimitive,
     {
-        let signed_value = value.to_i32().ok_or(AS1115Error::InvalidValue)?;
+        self.state.display_radix(value, 16, &FormatOptions::new())?;
+        self.commit()
+    }
 
-        // Check if value will fit
-        if signed_value >= 0 {
-            if signed_value as u32 > Self::max_unsigned_hex() {
-                return Err(AS1115Error::InvalidValue);
-            }
-        } else {
-            if -signed_value > Self::max_signed_hex() {
-                return Err(AS1115Error::InvalidValue);
-            }
-        }
+    /// Display an integer value in hexadecimal format laid out according to the given options, see `display_hex_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub fn display_hex_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, 16, options)?;
+        self.commit()
+    }
 
-        let is_negative = signed_value < 0;
-        let mut num = signed_value.unsigned_abs();
+    /// Display an integer value in the given base from 2 to 16 on the seven-segment display, using the hex digit glyphs above 9.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the base is invalid or the value is too large to fit in the display.
+    pub fn display_radix<T>(&mut self, value: T, base: u32) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state
+            .display_radix(value, base, &FormatOptions::new())?;
+        self.commit()
+    }
 
-        let mut digit_index = NUM_DIGITS;
+    /// Display an integer value in the given base laid out according to the given options, see `display_radix`.
+    /// Returns InvalidValue if the base is invalid or the value or the minimum width won't fit in the display.
+    pub fn display_radix_with<T>(
+        &mut self,
+        value: T,
+        base: u32,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_radix(value, base, options)?;
+        self.commit()
+    }
 
-        while digit_index > 0 && (num > 0 || digit_index == NUM_DIGITS) {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, NUMBERS[(num % 16) as usize])?;
-            num /= 16;
-        }
+    /// Display an integer value in binary format on the seven-segment display, see `display_radix`.
+    pub fn display_binary<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 2)
+    }
 
-        if is_negative {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, MINUS_SIGN)?;
-        }
+    /// Display an integer value in octal format on the seven-segment display, see `display_radix`.
+    pub fn display_octal<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 8)
+    }
 
-        while digit_index > 0 {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, 0)?;
-        }
+    /// Display a fixed-point value, value / 10^decimals, on the seven-segment display using only integer arithmetic.
+    /// The DP is placed after the units digit, values below 1 get a leading 0, and decimals that don't fit are rounded off.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the integer part doesn't fit in the display.
+    pub fn display_fixed(&mut self, value: i32, decimals: u8) -> Result<(), AS1115Error<E>> {
+        self.state
+            .display_fixed(value, decimals, &FormatOptions::new())?;
+        self.commit()
+    }
 
-        Ok(())
+    /// Display a fixed-point value laid out according to the given options, see `display_fixed`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    pub fn display_fixed_with(
+        &mut self,
+        value: i32,
+        decimals: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.display_fixed(value, decimals, options)?;
+        self.commit()
     }
 
     /// Display a floating-point decimal value on the seven-segment display.
@@ -264,170 +448,94 @@ where
     where
         T: ToPrimitive,
     {
-        let float_val = value.to_f32().ok_or(AS1115Error::InvalidValue)?;
-
-        if precision < 1 || precision >= NUM_DIGITS || !float_val.is_finite() {
-            return Err(AS1115Error::InvalidValue);
-        }
-
-        let is_negative = float_val.is_sign_negative();
-        let abs_val = float_val.abs();
-
-        // Check value bounds
-        let integer_digits = if abs_val < 1.0 {
-            1
-        } else {
-            let mut temp = abs_val as u32;
-            let mut digits = 0;
-            while temp > 0 {
-                digits += 1;
-                temp /= 10;
-            }
-            digits
-        };
-        let total_digits = (if is_negative { 1 } else { 0 }) + integer_digits + precision as u8;
-        if total_digits > NUM_DIGITS {
-            return Err(AS1115Error::InvalidValue);
-        }
-
-        // Scale number to integer value for formatting
-        let mut digit_index = NUM_DIGITS;
-        let mut scale_factor = 1.0f32;
-        for _ in 0..precision {
-            scale_factor *= 10.0;
-        }
-        let rounded_val = abs_val * scale_factor + 0.5;
-        let mut digits = rounded_val as u32;
-
-        for _ in 0..precision {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, NUMBERS[(digits % 10) as usize])?;
-            digits /= 10;
-        }
-
-        if digits == 0 {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, NUMBERS[0] | segments::DP)?;
-        } else {
-            let mut first_digit = true;
-            while digits > 0 {
-                // possible for rounding to cause overflow
-                if digit_index == 0 {
-                    return Err(AS1115Error::InvalidValue);
-                }
-
-                digit_index -= 1;
-
-                let mut segment_data = NUMBERS[digits as usize % 10];
-                if first_digit {
-                    segment_data |= segments::DP;
-                    first_digit = false;
-                }
-                self.set_digit_segment_data(digit_index, segment_data)?;
-                digits /= 10;
-            }
-        }
-
-        if is_negative {
-            digit_index -= 1;
-            self.set_digit_segment_data(digit_index, MINUS_SIGN)?;
-        }
+        self.state
+            .display_float_value(value, precision, &FormatOptions::new())?;
+        self.commit()
+    }
 
-        Ok(())
+    /// Display a floating-point decimal value laid out according to the given options, see `display_float_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the display.
+    #[cfg(feature = "display_float_value")]
+    pub fn display_float_value_with<T>(
+        &mut self,
+        value: T,
+        precision: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.state.display_float_value(value, precision, options)?;
+        self.commit()
     }
 
     /// Display raw segment data on the seven-segment display.
     /// Truncates extra segment data beyond NUM_DIGITS.
     pub fn display_segments(&mut self, segments: &[u8]) -> Result<(), AS1115Error<E>> {
-        for (index, &segment) in segments.iter().enumerate() {
-            self.set_digit_segment_data(index as u8, segment)?;
-            if index as u8 >= NUM_DIGITS {
-                break;
-            }
-        }
-        Ok(())
+        self.state.display_segments(segments);
+        self.commit()
     }
 
     /// Read keyscan data from 16 keys.
+    /// Returns the raw KEYA and KEYB register values in the high and low byte, see `read_key_state` for a typed result.
     pub fn read_keys(&mut self) -> Result<u16, AS1115Error<E>> {
-        let mut key_a = self.read_register(register::KEY_A)?;
+        let key_a = self.read_register(register::KEY_A)?;
         let key_b = self.read_register(register::KEY_B)?;
+        Ok(combine_keys(key_a, key_b, self.address != DEFAULT_ADDRESS))
+    }
 
-        // clear bits used for SEGG and SEGF pins on KEYA if self-addressing is enabled
-        if self.address != DEFAULT_ADDRESS {
-            key_a &= 0xFC;
-        }
-
-        Ok((key_a as u16) << 8 | (key_b as u16))
+    /// Read the set of pressed keys.
+    /// Keys shared with the self-addressing pins are never reported as pressed if self-addressing is used.
+    pub fn read_key_state(&mut self) -> Result<KeyState, AS1115Error<E>> {
+        let key_a = self.read_register(register::KEY_A)?;
+        let key_b = self.read_register(register::KEY_B)?;
+        Ok(KeyState::from_registers(
+            key_a,
+            key_b,
+            self.address != DEFAULT_ADDRESS,
+        ))
     }
 
     /// Set a specific digit to display an ASCII character.
     /// Returns InvalidLocation if the digit index is out of bounds.
     /// Returns InvalidValue if the character does not have a valid segment mapping.
     pub fn set_digit_ascii_char(&mut self, digit: u8, char: u8) -> Result<(), AS1115Error<E>> {
-        if digit >= NUM_DIGITS {
-            return Err(AS1115Error::InvalidLocation(digit));
-        }
-
-        let segments = ascii_to_segment(char);
-        if segments.is_none() {
-            return Err(AS1115Error::InvalidValue);
-        }
-
-        self.set_digit_segment_data(digit, segments.unwrap())
+        self.state.set_digit_ascii_char(digit, char)?;
+        self.commit()
     }
 
     /// Set a specific digit to display a hexadecimal digit.
     /// Returns InvalidLocation if the digit index is out of bounds.
-    /// Returns InvalidValue if the value is not a valid hexadecimal digit (0-15).
+    /// Returns InvalidValue if the value is not a valid hexadecimal digit (0-15), or if the digit uses Code-B decoding and the value is above 9.
     pub fn set_digit_hex_value(&mut self, digit: u8, value: u8) -> Result<(), AS1115Error<E>> {
-        if digit >= NUM_DIGITS {
-            return Err(AS1115Error::InvalidLocation(digit));
-        }
-        if value > 15 {
-            return Err(AS1115Error::InvalidValue);
-        }
-        let segments = NUMBERS[value as usize];
-        self.set_digit_segment_data(digit, segments)
+        self.state.set_digit_hex_value(digit, value)?;
+        self.commit()
     }
 
     /// Set a specific digit to display custom segment data.
+    /// For digits with hardware decoding enabled the data is sent as-is and interpreted as a decoder code.
     /// Returns InvalidLocation if the digit index is out of bounds.
     pub fn set_digit_segment_data(
         &mut self,
         digit: u8,
         segment_data: u8,
     ) -> Result<(), AS1115Error<E>> {
-        if digit >= NUM_DIGITS {
-            return Err(AS1115Error::InvalidLocation(digit));
-        }
-        self.write_register(register::DIGIT_OFFSET + digit, segment_data)?;
-        Ok(())
+        self.state.set_digit_segment_data(digit, segment_data)?;
+        self.commit()
     }
 
     /// Set a specific digit to display a decimal digit (0-9).
     /// Returns InvalidLocation if the digit index is out of bounds.
     /// Returns InvalidValue if the value is not a valid decimal digit (0-9).
     pub fn set_digit_value(&mut self, digit: u8, value: u8) -> Result<(), AS1115Error<E>> {
-        if digit >= NUM_DIGITS {
-            return Err(AS1115Error::InvalidLocation(digit));
-        }
-        if value > 9 {
-            return Err(AS1115Error::InvalidValue);
-        }
-        let segments = NUMBERS[value as usize];
-        self.set_digit_segment_data(digit, segments)
+        self.state.set_digit_value(digit, value)?;
+        self.commit()
     }
 
     /// Set the global intensity for all digits.
     /// Returns InvalidValue if the intensity value is out of range.
     pub fn set_intensity(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
-        if intensity > MAX_INTENSITY {
-            return Err(AS1115Error::InvalidValue);
-        }
-        for i in 0..NUM_DIGITS {
-            self.intensity[i as usize] = intensity;
-        }
+        self.state.set_intensity(intensity)?;
         self.write_register(register::GLOBAL_INTENSITY, intensity)?;
         Ok(())
     }
@@ -436,32 +544,8 @@ where
     /// Returns InvalidLocation if the digit index is out of bounds.
     /// Returns InvalidValue if the intensity value is out of range.
     pub fn set_digit_intensity(&mut self, digit: u8, intensity: u8) -> Result<(), AS1115Error<E>> {
-        if digit >= NUM_DIGITS {
-            return Err(AS1115Error::InvalidLocation(digit));
-        }
-        if intensity > MAX_INTENSITY {
-            return Err(AS1115Error::InvalidValue);
-        }
-
-        self.intensity[digit as usize] = intensity;
-
-        let register = match digit {
-            0..=1 => register::DIG01_INTENSITY,
-            2..=3 => register::DIG23_INTENSITY,
-            4..=5 => register::DIG45_INTENSITY,
-            6..=7 => register::DIG67_INTENSITY,
-            _ => return Err(AS1115Error::InvalidLocation(digit)),
-        };
-
-        // intensity register is read-only so we need a local cache to avoid overwriting paired digit's intensity
-        let reg_value = if digit % 2 == 0 {
-            self.intensity[(digit + 1) as usize] << 4 | self.intensity[digit as usize]
-        } else {
-            self.intensity[digit as usize] << 4 | self.intensity[(digit - 1) as usize]
-        };
-
-        self.write_register(register, reg_value)?;
-        Ok(())
+        let digit = self.state.set_digit_intensity(digit, intensity)?;
+        self.write_digit_intensity(digit)
     }
 
     /// Enable or disable display test mode (all LED segments on).
@@ -478,6 +562,64 @@ where
         Ok(())
     }
 
+    /// Enable hardware decoding for the digits set in the mask (bit 0 for digit 0) using the given decoder font.
+    /// Numeric display functions send decoder codes instead of segment data for decoded digits.
+    /// HEX decoding has no minus sign or blank glyph, so negative values are rejected and unused leading digits show 0.
+    /// Digits switching between decoded and segment data, or to another decoder font, are blanked and all digits are rewritten.
+    /// Returns InvalidValue if the mask contains digits beyond NUM_DIGITS, or any digits while the display is upside down.
+    pub fn set_decode_mode(
+        &mut self,
+        mask: u8,
+        decode_type: DecodeType,
+    ) -> Result<(), AS1115Error<E>> {
+        self.state.check_decode_mask(mask)?;
+        let hex = decode_type == DecodeType::Hex;
+        let decoder_changed = (self.state.feature & register::feature::DECODE_SET != 0) != hex;
+        self.update_feature(register::feature::DECODE_SET, hex)?;
+        self.write_register(register::DECODE_MODE, mask)?;
+        self.state.set_decode_mask(mask, decoder_changed);
+        self.commit()
+    }
+
+    /// Select the internal oscillator or the external CLK pin as system clock.
+    pub fn set_clock_source(&mut self, source: ClockSource) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::CLK_EN, source == ClockSource::External)
+    }
+
+    /// Returns the currently selected system clock source.
+    pub fn clock_source(&self) -> ClockSource {
+        self.state.clock_source()
+    }
+
+    /// Enable or disable hardware blinking of the whole display.
+    pub fn set_blink(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::BLINK_EN, enable)
+    }
+
+    /// Set the hardware blink frequency.
+    pub fn set_blink_frequency(&mut self, frequency: BlinkFrequency) -> Result<(), AS1115Error<E>> {
+        self.update_feature(
+            register::feature::BLINK_FREQ_SET,
+            frequency == BlinkFrequency::Slow,
+        )
+    }
+
+    /// Returns the typical blink period in milliseconds for the selected blink frequency.
+    /// Returns None when the external clock is selected, as the period then depends on the external clock frequency.
+    pub fn blink_period_ms(&self) -> Option<u16> {
+        self.state.blink_period_ms()
+    }
+
+    /// Set whether blinking starts with the display phase turned on or off.
+    pub fn set_blink_phase(&mut self, phase: BlinkPhase) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::BLINK_START, phase == BlinkPhase::On)
+    }
+
+    /// Enable or disable synchronizing blinking on the rising edge of the LD/CS pin.
+    pub fn set_blink_sync(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        self.update_feature(register::feature::SYNC, enable)
+    }
+
     /// Tests whether external resistor Rset is open.
     /// Returns true if Rset is detected as open, false otherwise.
     pub fn rset_test_open(&mut self) -> Result<bool, AS1115Error<E>> {
@@ -494,6 +636,117 @@ where
             != 0)
     }
 
+    /// Start an open or shorted LED test without waiting for it to finish.
+    /// Use `led_test_running` to poll for completion and `read_led_faults` to read the results.
+    pub fn start_led_test(&mut self, test: LedTest) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::DISPLAY_TEST_MODE,
+            State::<NUM_DIGITS>::led_test_mode(test),
+        )?;
+        Ok(())
+    }
+
+    /// Returns true while an open or shorted LED test is still in progress.
+    pub fn led_test_running(&mut self) -> Result<bool, AS1115Error<E>> {
+        Ok((self.read_register(register::DISPLAY_TEST_MODE)?
+            & register::display_test_mode::LED_TEST)
+            != 0)
+    }
+
+    /// Read the per-segment fault map of the last open or shorted LED test from the diagnostic registers.
+    pub fn read_led_faults(&mut self) -> Result<LedFaults, AS1115Error<E>> {
+        let mut faults = LedFaults::default();
+        for i in 0..NUM_DIGITS {
+            faults.digits[i as usize] = self.read_register(register::DIAG_DIGIT_0 + i)?;
+        }
+        Ok(faults)
+    }
+
+    /// Run an open or shorted LED test and wait for it to finish.
+    /// The test status is checked every millisecond using the given delay.
+    /// Returns the per-segment fault map, which is empty if the test detected no errors.
+    /// Returns Timeout if the test does not finish within timeout_ms milliseconds.
+    pub fn run_led_test<D: DelayNs>(
+        &mut self,
+        test: LedTest,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<LedFaults, AS1115Error<E>> {
+        self.start_led_test(test)?;
+
+        let mut waited_ms = 0;
+        let test_mode = loop {
+            let test_mode = self.read_register(register::DISPLAY_TEST_MODE)?;
+            if test_mode & register::display_test_mode::LED_TEST == 0 {
+                break test_mode;
+            }
+            if waited_ms >= timeout_ms {
+                return Err(AS1115Error::Timeout);
+            }
+            delay.delay_ms(1);
+            waited_ms += 1;
+        };
+
+        // skip reading the diagnostic registers if the test detected no errors
+        if test_mode & register::display_test_mode::LED_GLOBAL == 0 {
+            return Ok(LedFaults::default());
+        }
+        self.read_led_faults()
+    }
+
+    // writes the configuration following a feature reset once the AS1115 responds at its address
+    pub(crate) fn configure(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        self.write_register(register::DECODE_MODE, self.state.decode_mask)?;
+        self.write_register(register::SCAN_LIMIT, NUM_DIGITS - 1)?;
+        self.set_intensity(intensity)?;
+
+        // feature register was reset, restore any settings made before init
+        if self.state.feature != 0 {
+            self.write_register(register::FEATURE, self.state.feature)?;
+        }
+
+        // digit registers are undefined after power-up, make sure the next flush writes all of them
+        self.state.dirty = State::<NUM_DIGITS>::all_digits_mask();
+
+        Ok(())
+    }
+
+    // flushes staged digits right away unless writes are buffered
+    fn commit(&mut self) -> Result<(), AS1115Error<E>> {
+        if self.state.auto_flush() {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn write_digit_intensity(&mut self, digit: u8) -> Result<(), AS1115Error<E>> {
+        let (register, reg_value) = self.state.digit_intensity_register(digit)?;
+        self.write_register(register, reg_value)?;
+        Ok(())
+    }
+
+    fn write_shutdown_mode(
+        &mut self,
+        mode: u8,
+        preserve_feature: bool,
+    ) -> Result<(), AS1115Error<E>> {
+        self.write_register(
+            register::SHUTDOWN_MODE,
+            State::<NUM_DIGITS>::shutdown_mode_value(mode, preserve_feature),
+        )?;
+        if !preserve_feature {
+            self.state.feature = 0;
+        }
+        Ok(())
+    }
+
+    fn update_feature(&mut self, mask: u8, enable: bool) -> Result<(), AS1115Error<E>> {
+        let feature = self.state.feature_with(mask, enable);
+        self.write_register(register::FEATURE, feature)?;
+        self.state.feature = feature;
+        Ok(())
+    }
+
     fn read_register(&mut self, register: u8) -> Result<u8, AS1115Error<E>> {
         let mut buffer = [0; 1];
         self.i2c
@@ -506,6 +759,21 @@ where
         Ok(())
     }
 
+    fn write_registers(&mut self, register: u8, values: &[u8]) -> Result<(), AS1115Error<E>> {
+        if !self.state.burst_writes {
+            for (i, &value) in values.iter().enumerate() {
+                self.write_register(register + i as u8, value)?;
+            }
+            return Ok(());
+        }
+
+        let mut buffer = [0; MAX_DIGITS as usize + 1];
+        buffer[0] = register;
+        buffer[1..=values.len()].copy_from_slice(values);
+        self.i2c.write(self.address, &buffer[..=values.len()])?;
+        Ok(())
+    }
+
     fn write_register_to_addr(
         &mut self,
         address: u8,
@@ -516,3 +784,39 @@ where
         Ok(())
     }
 }
+
+impl<I2C, E, const NUM_DIGITS: u8, IRQ> AS1115<I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+    IRQ: InputPin,
+{
+    /// Read keyscan data only if the IRQ pin signals that it changed since the last read.
+    /// Returns None without any I2C traffic while the IRQ pin is inactive (high).
+    pub fn keys_changed(&mut self) -> Result<Option<KeyState>, AS1115Error<E>> {
+        if self.irq.is_high().map_err(|_| AS1115Error::IrqPinError)? {
+            return Ok(None);
+        }
+        Ok(Some(self.read_key_state()?))
+    }
+
+    /// Wait until the IRQ pin signals changed keyscan data, then read it.
+    /// The pin is checked every millisecond using the given delay, which can sleep between checks.
+    /// Returns Timeout if no change is signalled within timeout_ms milliseconds.
+    pub fn wait_for_keys<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<KeyState, AS1115Error<E>> {
+        let mut waited_ms = 0;
+        loop {
+            if let Some(key_state) = self.keys_changed()? {
+                return Ok(key_state);
+            }
+            if waited_ms >= timeout_ms {
+                return Err(AS1115Error::Timeout);
+            }
+            delay.delay_ms(1);
+            waited_ms += 1;
+        }
+    }
+}
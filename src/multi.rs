@@ -0,0 +1,168 @@
+use crate::state::State;
+use crate::{
+    register, AS1115Error, DigitMap, Font, LetterCase, NoIrq, SegmentMap, WriteMode, AS1115,
+    DEFAULT_ADDRESS,
+};
+use embedded_hal::i2c::I2c;
+
+/// Driver for several AS1115 chips sharing one I2C bus, each wired to its own self-addressing address.
+/// Individual chips are accessed by index through `with_chip`, shared settings can be applied to all chips at once.
+pub struct AS1115Multi<I2C, const NUM_DIGITS: u8, const NUM_CHIPS: usize> {
+    pub i2c: I2C,
+    pub(crate) addresses: [u8; NUM_CHIPS],
+    pub(crate) states: [State<NUM_DIGITS>; NUM_CHIPS],
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, const NUM_CHIPS: usize> AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new AS1115Multi instance with the given I2C interface and the address of each chip.
+    pub fn new(i2c: I2C, addresses: [u8; NUM_CHIPS]) -> Self {
+        Self {
+            i2c,
+            addresses,
+            states: core::array::from_fn(|_| State::new()),
+        }
+    }
+
+    /// Destroy the AS1115Multi instance and return the underlying I2C interface.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Returns the number of chips.
+    pub fn num_chips(&self) -> usize {
+        NUM_CHIPS
+    }
+
+    /// Returns the I2C address of the chip at the given index.
+    pub fn address(&self, index: usize) -> Option<u8> {
+        self.addresses.get(index).copied()
+    }
+
+    /// Initialize all chips with the given global intensity.
+    /// Wakes every chip at the factory address and switches them to their self-addressing addresses in one broadcast before configuring each chip.
+    pub fn init(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        self.i2c.write(
+            DEFAULT_ADDRESS,
+            &[
+                register::SHUTDOWN_MODE,
+                register::shutdown_mode::NORMAL_OPERATION | register::shutdown_mode::RESET_FEATURE,
+            ],
+        )?;
+
+        if self
+            .addresses
+            .iter()
+            .any(|&address| address != DEFAULT_ADDRESS)
+        {
+            self.i2c.write(
+                DEFAULT_ADDRESS,
+                &[
+                    register::SELF_ADDRESSING,
+                    register::self_addressing::USER_SET_ADDR,
+                ],
+            )?;
+        }
+
+        self.for_each_chip(|chip| chip.configure(intensity))
+    }
+
+    /// Run the given function with a driver for the chip at the given index.
+    /// Returns InvalidLocation if the index is out of bounds.
+    pub fn with_chip<R, F>(&mut self, index: usize, f: F) -> Result<R, AS1115Error<E>>
+    where
+        F: FnOnce(&mut AS1115<&mut I2C, NUM_DIGITS>) -> Result<R, AS1115Error<E>>,
+    {
+        if index >= NUM_CHIPS {
+            return Err(AS1115Error::InvalidLocation(
+                u8::try_from(index).unwrap_or(u8::MAX),
+            ));
+        }
+
+        // lend the chip's state to a driver borrowing the shared bus
+        let mut chip = AS1115 {
+            i2c: &mut self.i2c,
+            address: self.addresses[index],
+            irq: NoIrq,
+            state: core::mem::replace(&mut self.states[index], State::new()),
+        };
+        let result = f(&mut chip);
+        self.states[index] = chip.state;
+        result
+    }
+
+    /// Run the given function with a driver for each chip in index order, stopping at the first error.
+    pub fn for_each_chip<F>(&mut self, mut f: F) -> Result<(), AS1115Error<E>>
+    where
+        F: FnMut(&mut AS1115<&mut I2C, NUM_DIGITS>) -> Result<(), AS1115Error<E>>,
+    {
+        for index in 0..NUM_CHIPS {
+            self.with_chip(index, &mut f)?;
+        }
+        Ok(())
+    }
+
+    /// Set the global intensity of all chips.
+    /// Returns InvalidValue if the intensity value is out of range.
+    pub fn set_intensity(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.set_intensity(intensity))
+    }
+
+    /// Enable or disable hardware blinking on all chips.
+    pub fn set_blink(&mut self, enable: bool) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.set_blink(enable))
+    }
+
+    /// Set the write mode of all chips.
+    pub fn set_write_mode(&mut self, write_mode: WriteMode) {
+        for state in self.states.iter_mut() {
+            state.write_mode = write_mode;
+        }
+    }
+
+    /// Set the letter case mode of all chips using the built-in ASCII font, see `AS1115::set_letter_case`.
+    pub fn set_letter_case(&mut self, letter_case: LetterCase) {
+        for state in self.states.iter_mut() {
+            state.set_letter_case(letter_case);
+        }
+    }
+
+    /// Set the font of all chips.
+    pub fn set_font(&mut self, font: &'static dyn Font) {
+        for state in self.states.iter_mut() {
+            state.font = font;
+        }
+    }
+
+    /// Set the segment wiring of all chips.
+    pub fn set_segment_map(&mut self, segment_map: SegmentMap) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.set_segment_map(segment_map))
+    }
+
+    /// Set the digit order of all chips.
+    pub fn set_digit_map(&mut self, digit_map: DigitMap) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.set_digit_map(digit_map))
+    }
+
+    /// Put all chips into shutdown mode.
+    pub fn shutdown(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.shutdown(preserve_feature))
+    }
+
+    /// Wake all chips from shutdown mode.
+    pub fn wake(&mut self, preserve_feature: bool) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.wake(preserve_feature))
+    }
+
+    /// Clear the digit data of all chips.
+    pub fn clear(&mut self) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.clear())
+    }
+
+    /// Write the changed digits of all chips.
+    pub fn flush(&mut self) -> Result<(), AS1115Error<E>> {
+        self.for_each_chip(|chip| chip.flush())
+    }
+}
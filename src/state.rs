@@ -0,0 +1,452 @@
+use crate::format::{self, DigitSink, Glyph};
+use crate::{
+    code_b, register, segments, AS1115Error, ClockSource, DigitMap, LedTest, LetterCase, Rotation,
+    SegmentMap,
+};
+use crate::{
+    Font, FormatOptions, WriteMode, ASCII_CASED_FONT, ASCII_FONT, MAX_DIGITS, MAX_INTENSITY,
+    MINUS_SIGN, NUMBERS,
+};
+use num_traits::ToPrimitive;
+
+// Segment swaps of a digit turned upside down, DP is handled separately
+const UPSIDE_DOWN: SegmentMap = SegmentMap::IDENTITY
+    .swap(segments::A, segments::D)
+    .swap(segments::B, segments::E)
+    .swap(segments::C, segments::F);
+
+// Register caches, shadow digit buffer and formatting logic shared by the blocking and async drivers.
+// Display functions only stage digits here, the drivers write the dirty digits to the AS1115.
+pub(crate) struct State<const NUM_DIGITS: u8> {
+    pub(crate) intensity: [u8; MAX_DIGITS as usize], // ideally NUM_DIGITS
+    pub(crate) feature: u8,
+    pub(crate) decode_mask: u8,
+    pub(crate) digits: [u8; MAX_DIGITS as usize], // shadow of the digit registers
+    pub(crate) dirty: u8,                         // digits not yet written to the AS1115
+    pub(crate) write_mode: WriteMode,
+    pub(crate) burst_writes: bool,
+    pub(crate) font: &'static dyn Font,
+    pub(crate) segment_map: SegmentMap,
+    pub(crate) digit_map: DigitMap,
+    pub(crate) rotation: Rotation,
+}
+
+impl<const NUM_DIGITS: u8> State<NUM_DIGITS> {
+    const NUM_DIGITS_VALID: () = {
+        assert!(
+            NUM_DIGITS >= 1 && NUM_DIGITS <= 8,
+            "NUM_DIGITS must be between 1 and 8"
+        );
+    };
+
+    pub(crate) const fn all_digits_mask() -> u8 {
+        (0xFFu16 >> (MAX_DIGITS - NUM_DIGITS)) as u8
+    }
+
+    pub(crate) fn new() -> Self {
+        let () = Self::NUM_DIGITS_VALID;
+        Self {
+            intensity: [0; MAX_DIGITS as usize],
+            feature: 0,
+            decode_mask: register::decode_mode::NO_DECODE,
+            digits: [0; MAX_DIGITS as usize],
+            dirty: 0,
+            write_mode: WriteMode::Immediate,
+            burst_writes: true,
+            font: &ASCII_FONT,
+            segment_map: SegmentMap::IDENTITY,
+            digit_map: DigitMap::IDENTITY,
+            rotation: Rotation::Normal,
+        }
+    }
+
+    // returns true if staged digits should be flushed right away
+    pub(crate) fn auto_flush(&self) -> bool {
+        self.write_mode == WriteMode::Immediate
+    }
+
+    // returns the next run of consecutive dirty digits at or after start as a start..end range
+    pub(crate) fn next_dirty_run(&self, start: u8) -> Option<(u8, u8)> {
+        let start = (start..NUM_DIGITS).find(|&i| self.dirty & (1 << i) != 0)?;
+        let mut end = start + 1;
+        while end < NUM_DIGITS && self.dirty & (1 << end) != 0 {
+            end += 1;
+        }
+        Some((start, end))
+    }
+
+    pub(crate) fn mark_clean(&mut self, start: u8, end: u8) {
+        for i in start..end {
+            self.dirty &= !(1 << i);
+        }
+    }
+
+    // returns the digit register driving the given logical position
+    pub(crate) fn physical_digit(&self, position: u8) -> u8 {
+        Self::layout_digit(&self.digit_map, self.rotation, position)
+    }
+
+    fn layout_digit(digit_map: &DigitMap, rotation: Rotation, position: u8) -> u8 {
+        match rotation {
+            Rotation::Normal => digit_map.digit(position),
+            Rotation::UpsideDown => digit_map.digit(NUM_DIGITS - 1 - position),
+        }
+    }
+
+    // returns the staged data of the given logical position
+    pub(crate) fn digit_data(&self, position: u8) -> u8 {
+        self.digits[self.physical_digit(position) as usize]
+    }
+
+    pub(crate) fn stage_digit(&mut self, position: u8, data: u8) {
+        let digit = self.physical_digit(position);
+        let previous = self.digits[digit as usize];
+        if self.write_mode == WriteMode::Immediate || previous != data {
+            self.dirty |= 1 << digit;
+        }
+        // upside down, the DP is shown on the following digit
+        if self.rotation == Rotation::UpsideDown
+            && position + 1 < NUM_DIGITS
+            && (previous ^ data) & segments::DP != 0
+        {
+            self.dirty |= 1 << self.physical_digit(position + 1);
+        }
+        self.digits[digit as usize] = data;
+    }
+
+    // returns the digit register values with the rotation and segment map applied to digits without hardware decoding,
+    // a rotated display never has decoded digits
+    pub(crate) fn register_data(&self) -> [u8; MAX_DIGITS as usize] {
+        let mut data = self.digits;
+        if self.rotation == Rotation::UpsideDown {
+            for position in 0..NUM_DIGITS {
+                let digit = self.physical_digit(position) as usize;
+                data[digit] = UPSIDE_DOWN.apply(self.digits[digit] & !segments::DP);
+                if position > 0 && self.digit_data(position - 1) & segments::DP != 0 {
+                    data[digit] |= segments::DP;
+                }
+            }
+        }
+        for (digit, value) in data.iter_mut().enumerate() {
+            if self.decode_mask & (1 << digit) == 0 {
+                *value = self.segment_map.apply(*value);
+            }
+        }
+        data
+    }
+
+    // only switches between the built-in fonts, a custom font is kept
+    pub(crate) fn set_letter_case(&mut self, letter_case: LetterCase) {
+        let builtin = core::ptr::addr_eq(self.font, &ASCII_FONT)
+            || core::ptr::addr_eq(self.font, &ASCII_CASED_FONT);
+        if builtin {
+            self.font = match letter_case {
+                LetterCase::Insensitive => &ASCII_FONT,
+                LetterCase::Sensitive => &ASCII_CASED_FONT,
+            };
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        format::clear(self);
+    }
+
+    pub(crate) fn display_ascii(&mut self, chars: &[u8]) {
+        format::ascii(self, chars);
+    }
+
+    pub(crate) fn display_radix<E, T>(
+        &mut self,
+        value: T,
+        base: u32,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(self, value, base, options)
+    }
+
+    pub(crate) fn display_fixed<E>(
+        &mut self,
+        value: i32,
+        decimals: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>> {
+        format::fixed(self, value, decimals, options)
+    }
+
+    #[cfg(feature = "display_float_value")]
+    pub(crate) fn display_float_value<E, T>(
+        &mut self,
+        value: T,
+        precision: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        let value = value.to_f32().ok_or(AS1115Error::InvalidValue)?;
+        format::float(self, value, precision, options)
+    }
+
+    pub(crate) fn display_segments(&mut self, segments: &[u8]) {
+        format::segment_data(self, segments);
+    }
+
+    pub(crate) fn set_digit_ascii_char<E>(
+        &mut self,
+        digit: u8,
+        char: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        if digit >= NUM_DIGITS {
+            return Err(AS1115Error::InvalidLocation(digit));
+        }
+
+        let segments = self.char_segments(char as char);
+        if segments.is_none() {
+            return Err(AS1115Error::InvalidValue);
+        }
+
+        self.stage_digit(digit, segments.unwrap());
+        Ok(())
+    }
+
+    pub(crate) fn set_digit_hex_value<E>(
+        &mut self,
+        digit: u8,
+        value: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        if digit >= NUM_DIGITS {
+            return Err(AS1115Error::InvalidLocation(digit));
+        }
+        if value > 15 {
+            return Err(AS1115Error::InvalidValue);
+        }
+        let data = self.glyph_data(digit, Glyph::Numeral(value), false)?;
+        self.stage_digit(digit, data);
+        Ok(())
+    }
+
+    pub(crate) fn set_digit_segment_data<E>(
+        &mut self,
+        digit: u8,
+        segment_data: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        if digit >= NUM_DIGITS {
+            return Err(AS1115Error::InvalidLocation(digit));
+        }
+        self.stage_digit(digit, segment_data);
+        Ok(())
+    }
+
+    pub(crate) fn set_digit_value<E>(
+        &mut self,
+        digit: u8,
+        value: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        if digit >= NUM_DIGITS {
+            return Err(AS1115Error::InvalidLocation(digit));
+        }
+        if value > 9 {
+            return Err(AS1115Error::InvalidValue);
+        }
+        let data = self.glyph_data(digit, Glyph::Numeral(value), false)?;
+        self.stage_digit(digit, data);
+        Ok(())
+    }
+
+    pub(crate) fn set_intensity<E>(&mut self, intensity: u8) -> Result<(), AS1115Error<E>> {
+        if intensity > MAX_INTENSITY {
+            return Err(AS1115Error::InvalidValue);
+        }
+        for i in 0..NUM_DIGITS {
+            self.intensity[i as usize] = intensity;
+        }
+        Ok(())
+    }
+
+    // returns the digit register of the given logical position for writing its intensity
+    pub(crate) fn set_digit_intensity<E>(
+        &mut self,
+        position: u8,
+        intensity: u8,
+    ) -> Result<u8, AS1115Error<E>> {
+        if position >= NUM_DIGITS {
+            return Err(AS1115Error::InvalidLocation(position));
+        }
+        if intensity > MAX_INTENSITY {
+            return Err(AS1115Error::InvalidValue);
+        }
+        let digit = self.physical_digit(position);
+        self.intensity[digit as usize] = intensity;
+        Ok(digit)
+    }
+
+    // moves digit data and intensities to the registers of the new map and marks all digits dirty
+    pub(crate) fn set_layout<E>(
+        &mut self,
+        digit_map: DigitMap,
+        rotation: Rotation,
+    ) -> Result<(), AS1115Error<E>> {
+        if (0..NUM_DIGITS).any(|position| digit_map.digit(position) >= NUM_DIGITS) {
+            return Err(AS1115Error::InvalidValue);
+        }
+        if rotation == Rotation::UpsideDown && self.decode_mask != 0 {
+            return Err(AS1115Error::InvalidValue);
+        }
+        let digits = self.digits;
+        let intensity = self.intensity;
+        for position in 0..NUM_DIGITS {
+            let from = self.physical_digit(position) as usize;
+            let to = Self::layout_digit(&digit_map, rotation, position) as usize;
+            self.digits[to] = digits[from];
+            self.intensity[to] = intensity[from];
+        }
+        self.digit_map = digit_map;
+        self.rotation = rotation;
+        self.dirty = Self::all_digits_mask();
+        Ok(())
+    }
+
+    // returns the intensity register and value for the pair of digits containing the given digit
+    pub(crate) fn digit_intensity_register<E>(
+        &self,
+        digit: u8,
+    ) -> Result<(u8, u8), AS1115Error<E>> {
+        let register = match digit {
+            0..=1 => register::DIG01_INTENSITY,
+            2..=3 => register::DIG23_INTENSITY,
+            4..=5 => register::DIG45_INTENSITY,
+            6..=7 => register::DIG67_INTENSITY,
+            _ => return Err(AS1115Error::InvalidLocation(digit)),
+        };
+
+        // intensity register is read-only so we need a local cache to avoid overwriting paired digit's intensity
+        let reg_value = if digit.is_multiple_of(2) {
+            self.intensity[(digit + 1) as usize] << 4 | self.intensity[digit as usize]
+        } else {
+            self.intensity[digit as usize] << 4 | self.intensity[(digit - 1) as usize]
+        };
+
+        Ok((register, reg_value))
+    }
+
+    // the decoder font can't be rotated, so an upside-down display can't have decoded digits
+    pub(crate) fn check_decode_mask<E>(&self, mask: u8) -> Result<(), AS1115Error<E>> {
+        if NUM_DIGITS < MAX_DIGITS && mask >> NUM_DIGITS != 0 {
+            return Err(AS1115Error::InvalidValue);
+        }
+        if mask != 0 && self.rotation == Rotation::UpsideDown {
+            return Err(AS1115Error::InvalidValue);
+        }
+        Ok(())
+    }
+
+    // blanks the digits whose data no longer means the same with the new mask or decoder font and marks all digits dirty
+    pub(crate) fn set_decode_mask(&mut self, mask: u8, decoder_changed: bool) {
+        let changed = if decoder_changed {
+            self.decode_mask | mask
+        } else {
+            self.decode_mask ^ mask
+        };
+        self.decode_mask = mask;
+        let hex = self.feature & register::feature::DECODE_SET != 0;
+        for digit in 0..NUM_DIGITS {
+            if changed & (1 << digit) == 0 {
+                continue;
+            }
+            let blank_code = mask & (1 << digit) != 0 && !hex;
+            self.digits[digit as usize] = if blank_code { code_b::BLANK } else { 0 };
+        }
+        self.dirty = Self::all_digits_mask();
+    }
+
+    // feature register is written as a whole so we keep a local cache to preserve the other feature bits
+    pub(crate) fn feature_with(&self, mask: u8, enable: bool) -> u8 {
+        if enable {
+            self.feature | mask
+        } else {
+            self.feature & !mask
+        }
+    }
+
+    pub(crate) fn shutdown_mode_value(mode: u8, preserve_feature: bool) -> u8 {
+        if preserve_feature {
+            mode | register::shutdown_mode::PRESERVE_FEATURE
+        } else {
+            mode | register::shutdown_mode::RESET_FEATURE
+        }
+    }
+
+    pub(crate) fn clock_source(&self) -> ClockSource {
+        if self.feature & register::feature::CLK_EN != 0 {
+            ClockSource::External
+        } else {
+            ClockSource::Internal
+        }
+    }
+
+    pub(crate) fn blink_period_ms(&self) -> Option<u16> {
+        if self.clock_source() == ClockSource::External {
+            return None;
+        }
+        if self.feature & register::feature::BLINK_FREQ_SET != 0 {
+            Some(2000)
+        } else {
+            Some(1000)
+        }
+    }
+
+    pub(crate) fn led_test_mode(test: LedTest) -> u8 {
+        match test {
+            LedTest::Open => register::display_test_mode::LED_OPEN,
+            LedTest::Short => register::display_test_mode::LED_SHORT,
+        }
+    }
+}
+
+impl<const NUM_DIGITS: u8> DigitSink for State<NUM_DIGITS> {
+    fn num_digits(&self) -> u8 {
+        NUM_DIGITS
+    }
+
+    fn stage_segments(&mut self, position: u8, data: u8) {
+        self.stage_digit(position, data);
+    }
+
+    fn char_segments(&self, c: char) -> Option<u8> {
+        self.font.glyph(c)
+    }
+
+    fn glyph_data<E>(&self, position: u8, glyph: Glyph, dp: bool) -> Result<u8, AS1115Error<E>> {
+        let data = if self.decode_mask & (1 << self.physical_digit(position)) != 0 {
+            let hex = self.feature & register::feature::DECODE_SET != 0;
+            match glyph {
+                Glyph::Numeral(n) if n <= 9 || hex => n,
+                Glyph::Minus if !hex => code_b::MINUS,
+                Glyph::Blank if hex => 0,
+                Glyph::Blank => code_b::BLANK,
+                _ => return Err(AS1115Error::InvalidValue),
+            }
+        } else {
+            match glyph {
+                Glyph::Numeral(n) => NUMBERS[n as usize],
+                Glyph::Minus => MINUS_SIGN,
+                Glyph::Blank => 0,
+                Glyph::Segments(data) => data,
+            }
+        };
+        Ok(if dp { data | segments::DP } else { data })
+    }
+}
+
+// combine KEYA and KEYB into a single value, KEYA in the high byte
+pub(crate) fn combine_keys(mut key_a: u8, key_b: u8, self_addressing: bool) -> u16 {
+    // clear bits used for SEGG and SEGF pins on KEYA if self-addressing is enabled
+    if self_addressing {
+        key_a &= 0xFC;
+    }
+
+    (key_a as u16) << 8 | (key_b as u16)
+}
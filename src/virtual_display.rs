@@ -0,0 +1,267 @@
+use crate::format::{self, DigitSink, Glyph};
+use crate::state::State;
+use crate::{AS1115Error, AS1115Multi, FormatOptions};
+use embedded_hal::i2c::I2c;
+use num_traits::ToPrimitive;
+
+// Digit states of all chips seen as one run of positions, chip 0 holding the leftmost digits
+struct Span<'a, const NUM_DIGITS: u8, const NUM_CHIPS: usize>(
+    &'a mut [State<NUM_DIGITS>; NUM_CHIPS],
+);
+
+impl<const NUM_DIGITS: u8, const NUM_CHIPS: usize> DigitSink for Span<'_, NUM_DIGITS, NUM_CHIPS> {
+    fn num_digits(&self) -> u8 {
+        NUM_DIGITS * NUM_CHIPS as u8
+    }
+
+    fn stage_segments(&mut self, position: u8, data: u8) {
+        self.0[(position / NUM_DIGITS) as usize].stage_digit(position % NUM_DIGITS, data);
+    }
+
+    // text uses the font of the first chip
+    fn char_segments(&self, c: char) -> Option<u8> {
+        self.0[0].char_segments(c)
+    }
+
+    fn glyph_data<E>(&self, position: u8, glyph: Glyph, dp: bool) -> Result<u8, AS1115Error<E>> {
+        self.0[(position / NUM_DIGITS) as usize].glyph_data(position % NUM_DIGITS, glyph, dp)
+    }
+}
+
+/// Logical display joining the chips of an `AS1115Multi` into one digit space of NUM_DIGITS * NUM_CHIPS digits.
+/// Chip 0 holds the leftmost digits, values and text are split across chip boundaries automatically.
+pub struct VirtualDisplay<I2C, const NUM_DIGITS: u8, const NUM_CHIPS: usize> {
+    multi: AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS>,
+}
+
+impl<I2C, E, const NUM_DIGITS: u8, const NUM_CHIPS: usize>
+    VirtualDisplay<I2C, NUM_DIGITS, NUM_CHIPS>
+where
+    I2C: I2c<Error = E>,
+{
+    const NUM_CHIPS_VALID: () = {
+        assert!(
+            NUM_CHIPS >= 1 && NUM_DIGITS as usize * NUM_CHIPS <= u8::MAX as usize,
+            "NUM_CHIPS must be at least 1 and NUM_DIGITS * NUM_CHIPS at most 255"
+        );
+    };
+
+    /// Create a new VirtualDisplay spanning all chips of the given AS1115Multi.
+    pub fn new(multi: AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS>) -> Self {
+        let () = Self::NUM_CHIPS_VALID;
+        Self { multi }
+    }
+
+    /// Destroy the VirtualDisplay instance and return the underlying AS1115Multi.
+    pub fn destroy(self) -> AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS> {
+        self.multi
+    }
+
+    /// Returns the underlying AS1115Multi, e.g. to access a single chip.
+    pub fn multi(&self) -> &AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS> {
+        &self.multi
+    }
+
+    /// Returns the underlying AS1115Multi mutably, e.g. to configure a single chip with `with_chip`.
+    pub fn multi_mut(&mut self) -> &mut AS1115Multi<I2C, NUM_DIGITS, NUM_CHIPS> {
+        &mut self.multi
+    }
+
+    /// Returns the total number of digits across all chips.
+    pub fn num_digits(&self) -> u8 {
+        NUM_DIGITS * NUM_CHIPS as u8
+    }
+
+    /// Clear the digit data of all chips.
+    pub fn clear(&mut self) -> Result<(), AS1115Error<E>> {
+        format::clear(&mut Span(&mut self.multi.states));
+        self.commit()
+    }
+
+    /// Display best-effort ASCII characters across the chips.
+    /// Skips over any characters that do not have a valid segment mapping.
+    /// Decimal points are included using the DP segment of the preceding digit.
+    /// Truncates the input to fit the total number of digits.
+    /// Text is sent as segment data, which digits with hardware decoding show as decoder glyphs instead, so keep text on digits without decoding.
+    pub fn display_ascii(&mut self, chars: &[u8]) -> Result<(), AS1115Error<E>> {
+        format::ascii(&mut Span(&mut self.multi.states), chars);
+        self.commit()
+    }
+
+    /// Display ASCII characters starting at the given digit position, which may be negative or past the last digit.
+    /// Digits not covered by the text are blanked, so stepping the offset from num_digits down to -(text length) scrolls the text across the display.
+    pub fn display_ascii_at(&mut self, chars: &[u8], offset: i16) -> Result<(), AS1115Error<E>> {
+        format::ascii_at(&mut Span(&mut self.multi.states), chars, offset);
+        self.commit()
+    }
+
+    /// Display a decimal value right-aligned across the chips.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the value won't fit in the total number of digits.
+    pub fn display_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(
+            &mut Span(&mut self.multi.states),
+            value,
+            10,
+            &FormatOptions::new(),
+        )?;
+        self.commit()
+    }
+
+    /// Display a decimal value across the chips laid out according to the given options, see `display_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the total number of digits.
+    pub fn display_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(&mut Span(&mut self.multi.states), value, 10, options)?;
+        self.commit()
+    }
+
+    /// Display a hexadecimal value right-aligned across the chips.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the value won't fit in the total number of digits.
+    pub fn display_hex_value<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(
+            &mut Span(&mut self.multi.states),
+            value,
+            16,
+            &FormatOptions::new(),
+        )?;
+        self.commit()
+    }
+
+    /// Display a hexadecimal value across the chips laid out according to the given options, see `display_hex_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the total number of digits.
+    pub fn display_hex_value_with<T>(
+        &mut self,
+        value: T,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(&mut Span(&mut self.multi.states), value, 16, options)?;
+        self.commit()
+    }
+
+    /// Display a value in the given base from 2 to 16 right-aligned across the chips, using the hex digit glyphs above 9.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the base is invalid or the value won't fit in the total number of digits.
+    pub fn display_radix<T>(&mut self, value: T, base: u32) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix_with(value, base, &FormatOptions::new())
+    }
+
+    /// Display a value in the given base across the chips laid out according to the given options, see `display_radix`.
+    /// Returns InvalidValue if the base is invalid or the value or the minimum width won't fit in the total number of digits.
+    pub fn display_radix_with<T>(
+        &mut self,
+        value: T,
+        base: u32,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        format::integer(&mut Span(&mut self.multi.states), value, base, options)?;
+        self.commit()
+    }
+
+    /// Display a binary value right-aligned across the chips, see `display_radix`.
+    pub fn display_binary<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 2)
+    }
+
+    /// Display an octal value right-aligned across the chips, see `display_radix`.
+    pub fn display_octal<T>(&mut self, value: T) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        self.display_radix(value, 8)
+    }
+
+    /// Display a floating-point decimal value right-aligned across the chips.
+    /// Supports negative numbers by prepending a minus sign.
+    /// Returns InvalidValue if the value won't fit with the given precision or if the precision value is invalid.
+    #[cfg(feature = "display_float_value")]
+    pub fn display_float_value<T>(&mut self, value: T, precision: u8) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        let value = value.to_f32().ok_or(AS1115Error::InvalidValue)?;
+        format::float(
+            &mut Span(&mut self.multi.states),
+            value,
+            precision,
+            &FormatOptions::new(),
+        )?;
+        self.commit()
+    }
+
+    /// Display a floating-point decimal value across the chips laid out according to the given options, see `display_float_value`.
+    /// Returns InvalidValue if the value or the minimum width won't fit in the total number of digits.
+    #[cfg(feature = "display_float_value")]
+    pub fn display_float_value_with<T>(
+        &mut self,
+        value: T,
+        precision: u8,
+        options: &FormatOptions,
+    ) -> Result<(), AS1115Error<E>>
+    where
+        T: ToPrimitive,
+    {
+        let value = value.to_f32().ok_or(AS1115Error::InvalidValue)?;
+        format::float(&mut Span(&mut self.multi.states), value, precision, options)?;
+        self.commit()
+    }
+
+    /// Display raw segment data across the chips, one byte per digit.
+    /// Truncates the input to fit the total number of digits.
+    pub fn display_segments(&mut self, segments: &[u8]) -> Result<(), AS1115Error<E>> {
+        format::segment_data(&mut Span(&mut self.multi.states), segments);
+        self.commit()
+    }
+
+    /// Set the intensity of the digit at the given position.
+    /// Returns InvalidLocation if the position is out of bounds.
+    /// Returns InvalidValue if the intensity value is out of range.
+    pub fn set_digit_intensity(
+        &mut self,
+        position: u8,
+        intensity: u8,
+    ) -> Result<(), AS1115Error<E>> {
+        if position >= self.num_digits() {
+            return Err(AS1115Error::InvalidLocation(position));
+        }
+        let digit = position % NUM_DIGITS;
+        self.multi
+            .with_chip((position / NUM_DIGITS) as usize, |chip| {
+                chip.set_digit_intensity(digit, intensity)
+            })
+    }
+
+    /// Write the changed digits of all chips.
+    pub fn flush(&mut self) -> Result<(), AS1115Error<E>> {
+        self.multi.flush()
+    }
+
+    fn commit(&mut self) -> Result<(), AS1115Error<E>> {
+        self.multi.for_each_chip(|chip| chip.commit())
+    }
+}
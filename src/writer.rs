@@ -0,0 +1,107 @@
+use crate::format::DigitSink;
+use crate::{segments, AS1115Error, AS1115};
+use embedded_hal::i2c::I2c;
+
+/// Text writer lent out by `AS1115::writer`, implementing `core::fmt::Write` (and `ufmt::uWrite` with the `ufmt` feature).
+/// Characters are placed left to right like `display_ascii`, with '.' merged into the previous digit's DP.
+/// Characters past the last digit are dropped. On `finish` or drop the remaining digits are blanked and the digits are written.
+pub struct DisplayWriter<'a, I2C, const NUM_DIGITS: u8, IRQ>
+where
+    I2C: I2c,
+{
+    display: &'a mut AS1115<I2C, NUM_DIGITS, IRQ>,
+    position: u8,
+    dp_allowed: bool,
+    finished: bool,
+}
+
+impl<'a, I2C, E, const NUM_DIGITS: u8, IRQ> DisplayWriter<'a, I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c<Error = E>,
+{
+    pub(crate) fn new(display: &'a mut AS1115<I2C, NUM_DIGITS, IRQ>) -> Self {
+        Self {
+            display,
+            position: 0,
+            dp_allowed: false,
+            finished: false,
+        }
+    }
+
+    /// Blank the digits that were not written to and write the digits to the AS1115.
+    /// Dropping the writer does the same but discards any I2C error.
+    pub fn finish(mut self) -> Result<(), AS1115Error<E>> {
+        self.complete()
+    }
+
+    fn complete(&mut self) -> Result<(), AS1115Error<E>> {
+        self.finished = true;
+        for digit in self.position..NUM_DIGITS {
+            self.display.state.stage_blank(digit);
+        }
+        self.display.commit()
+    }
+
+    fn put_char(&mut self, c: char) {
+        if c == '.' && self.dp_allowed {
+            let digit = self.position - 1;
+            let data = self.display.state.digit_data(digit) | segments::DP;
+            self.display.state.stage_segments(digit, data);
+            self.dp_allowed = false;
+            return;
+        }
+
+        let Some(segment_data) = self.display.state.char_segments(c) else {
+            return;
+        };
+        if self.position < NUM_DIGITS {
+            self.display
+                .state
+                .stage_segments(self.position, segment_data);
+            self.position += 1;
+            self.dp_allowed = true;
+        } else {
+            self.dp_allowed = false;
+        }
+    }
+
+    fn write_text(&mut self, s: &str) {
+        for c in s.chars() {
+            self.put_char(c);
+        }
+    }
+}
+
+impl<I2C, const NUM_DIGITS: u8, IRQ> Drop for DisplayWriter<'_, I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c,
+{
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.complete();
+        }
+    }
+}
+
+impl<I2C, const NUM_DIGITS: u8, IRQ> core::fmt::Write for DisplayWriter<'_, I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c,
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write_text(s);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<I2C, const NUM_DIGITS: u8, IRQ> ufmt_write::uWrite for DisplayWriter<'_, I2C, NUM_DIGITS, IRQ>
+where
+    I2C: I2c,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.write_text(s);
+        Ok(())
+    }
+}
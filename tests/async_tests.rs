@@ -0,0 +1,39 @@
+#![cfg(feature = "async")]
+
+extern crate as1115;
+
+mod common;
+
+use as1115::{register, LedTest, NUMBERS};
+use common::{MockDelay, RegisterMock};
+use embassy_futures::block_on;
+
+const NUM_DIGITS: u8 = 4;
+
+#[test]
+fn async_display_value() {
+    let mut display: as1115::AS1115Async<_, NUM_DIGITS> =
+        as1115::AS1115Async::new(RegisterMock::new());
+
+    block_on(display.init(3)).unwrap();
+    block_on(display.display_value(-12)).unwrap();
+    assert!(block_on(display.display_value(10000)).is_err());
+
+    let i2c = display.destroy();
+    assert_eq!(i2c.registers[register::GLOBAL_INTENSITY as usize], 3);
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [0, as1115::MINUS_SIGN, NUMBERS[1], NUMBERS[2]]
+    );
+}
+
+#[test]
+fn async_led_test() {
+    let mut i2c = RegisterMock::new();
+    i2c.registers[register::DISPLAY_TEST_MODE as usize] = register::display_test_mode::LED_GLOBAL;
+    i2c.registers[register::DIAG_DIGIT_3 as usize] = as1115::segments::G;
+
+    let mut display: as1115::AS1115Async<_, NUM_DIGITS> = as1115::AS1115Async::new(i2c);
+    let faults = block_on(display.run_led_test(LedTest::Short, &mut MockDelay(0), 10)).unwrap();
+    assert!(faults.is_faulty(3, as1115::segments::G));
+}
@@ -0,0 +1,86 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{register, WriteMode, NUMBERS};
+use common::RegisterMock;
+
+const NUM_DIGITS: u8 = 4;
+
+fn digit_writes(i2c: &RegisterMock) -> usize {
+    i2c.writes
+        .iter()
+        .filter(|(_, data)| (register::DIGIT_OFFSET..register::DECODE_MODE).contains(&data[0]))
+        .map(|(_, data)| data.len() - 1)
+        .sum()
+}
+
+#[test]
+fn buffered_writes_only_changed_digits() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.set_write_mode(WriteMode::Buffered);
+
+    display.display_value(1234).unwrap();
+    assert_eq!(digit_writes(&display.i2c), 0);
+    display.flush().unwrap();
+    assert_eq!(digit_writes(&display.i2c), 4);
+
+    display.display_value(1235).unwrap();
+    display.flush().unwrap();
+    assert_eq!(digit_writes(&display.i2c), 5);
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize + 3],
+        NUMBERS[5]
+    );
+}
+
+#[test]
+fn reset_registers_marks_digits_dirty() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.set_write_mode(WriteMode::Buffered);
+
+    display.display_value(1234).unwrap();
+    display.flush().unwrap();
+    display.i2c.registers = [0; 0x30]; // digit registers back to defaults
+    display.reset_registers(false).unwrap();
+
+    display.display_value(1235).unwrap();
+    display.flush().unwrap();
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [NUMBERS[1], NUMBERS[2], NUMBERS[3], NUMBERS[5]]
+    );
+}
+
+#[test]
+fn immediate_writes_every_digit() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.display_value(1234).unwrap();
+    display.display_value(1234).unwrap();
+    assert_eq!(digit_writes(&display.i2c), 8);
+}
+
+#[test]
+fn burst_writes_consecutive_digits() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.display_value(1234).unwrap();
+    assert_eq!(display.i2c.writes.len(), 1);
+    assert_eq!(
+        display.i2c.writes[0].1,
+        [
+            register::DIGIT_OFFSET,
+            NUMBERS[1],
+            NUMBERS[2],
+            NUMBERS[3],
+            NUMBERS[4]
+        ]
+    );
+
+    display.set_burst_writes(false);
+    display.clear().unwrap();
+    assert_eq!(display.i2c.writes.len(), 5);
+}
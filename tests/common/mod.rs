@@ -0,0 +1,89 @@
+#![allow(dead_code)]
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+
+/// Mock I2C bus that simulates the AS1115 register file and records every write transaction.
+pub struct RegisterMock {
+    pub registers: [u8; 0x30],
+    pub writes: Vec<(u8, Vec<u8>)>,
+}
+
+impl RegisterMock {
+    pub fn new() -> Self {
+        Self {
+            registers: [0; 0x30],
+            writes: Vec::new(),
+        }
+    }
+}
+
+impl ErrorType for RegisterMock {
+    type Error = ErrorKind;
+}
+
+impl I2c for RegisterMock {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut pointer = 0usize;
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    self.writes.push((address, data.to_vec()));
+                    pointer = data[0] as usize;
+                    for &value in &data[1..] {
+                        // only the lower three bits of the display test register are writable
+                        self.registers[pointer] = if pointer == 0x0F {
+                            (self.registers[pointer] & !0x07) | (value & 0x07)
+                        } else {
+                            value
+                        };
+                        pointer += 1;
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for value in buffer.iter_mut() {
+                        *value = self.registers[pointer];
+                        pointer += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Delay that only counts the nanoseconds waited.
+pub struct MockDelay(pub u64);
+
+impl embedded_hal::delay::DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0 += ns as u64;
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0 += ns as u64;
+    }
+}
+
+/// Digit registers of a display on the mock bus, DIGIT0 first.
+pub fn digits<const N: u8>(display: &as1115::AS1115<RegisterMock, N>) -> &[u8] {
+    let start = as1115::register::DIGIT_OFFSET as usize;
+    &display.i2c.registers[start..start + N as usize]
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for RegisterMock {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
@@ -0,0 +1,54 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{register, segments, LedTest};
+use common::{MockDelay, RegisterMock};
+
+const NUM_DIGITS: u8 = 4;
+
+#[test]
+fn led_test_reads_faults() {
+    let mut i2c = RegisterMock::new();
+    i2c.registers[register::DISPLAY_TEST_MODE as usize] = register::display_test_mode::LED_GLOBAL;
+    i2c.registers[register::DIAG_DIGIT_1 as usize] = segments::A | segments::DP;
+
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(i2c);
+    let faults = display
+        .run_led_test(LedTest::Open, &mut MockDelay(0), 10)
+        .unwrap();
+
+    assert!(faults.has_faults());
+    assert!(faults.is_faulty(1, segments::A));
+    assert!(!faults.is_faulty(1, segments::G));
+    assert_eq!(faults.digit(0), 0);
+    assert_eq!(faults.digit(8), 0);
+}
+
+#[test]
+fn led_test_without_errors() {
+    let mut i2c = RegisterMock::new();
+    i2c.registers[register::DIAG_DIGIT_0 as usize] = 0xFF;
+
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(i2c);
+    let faults = display
+        .run_led_test(LedTest::Short, &mut MockDelay(0), 10)
+        .unwrap();
+
+    // diagnostic registers are ignored when LED_GLOBAL is not set
+    assert!(!faults.has_faults());
+}
+
+#[test]
+fn led_test_times_out() {
+    let mut i2c = RegisterMock::new();
+    i2c.registers[register::DISPLAY_TEST_MODE as usize] = register::display_test_mode::LED_TEST;
+
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(i2c);
+    let mut delay = MockDelay(0);
+    assert!(matches!(
+        display.run_led_test(LedTest::Open, &mut delay, 5),
+        Err(as1115::AS1115Error::Timeout)
+    ));
+    assert_eq!(delay.0, 5_000_000);
+}
@@ -0,0 +1,234 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{register, BlinkFrequency, BlinkPhase, ClockSource};
+use common::RegisterMock;
+
+const NUM_DIGITS: u8 = 4;
+
+#[test]
+fn blink_preserves_feature_bits() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.set_blink(true).unwrap();
+    display.set_blink_frequency(BlinkFrequency::Slow).unwrap();
+    display.set_blink_phase(BlinkPhase::On).unwrap();
+    display.set_blink_sync(true).unwrap();
+    display.set_blink_frequency(BlinkFrequency::Fast).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.registers[register::FEATURE as usize],
+        register::feature::BLINK_EN | register::feature::BLINK_START | register::feature::SYNC
+    );
+}
+
+#[test]
+fn decoded_digits_receive_codes() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display
+        .set_decode_mode(0b0011, as1115::DecodeType::CodeB)
+        .unwrap();
+    display.display_value(-42).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(i2c.registers[register::DECODE_MODE as usize], 0b0011);
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize],
+        as1115::code_b::BLANK
+    );
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize + 1],
+        as1115::code_b::MINUS
+    );
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize + 2],
+        as1115::NUMBERS[4]
+    );
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize + 3],
+        as1115::NUMBERS[2]
+    );
+}
+
+#[test]
+fn clear_blanks_decoded_digits() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display
+        .set_decode_mode(0b0011, as1115::DecodeType::CodeB)
+        .unwrap();
+    display.display_value(1234).unwrap();
+    display.clear().unwrap();
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [as1115::code_b::BLANK, as1115::code_b::BLANK, 0, 0]
+    );
+
+    // digits left over by the writer are blanked the same way
+    display.display_value(1234).unwrap();
+    display.writer().finish().unwrap();
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [as1115::code_b::BLANK, as1115::code_b::BLANK, 0, 0]
+    );
+}
+
+#[test]
+fn decode_mode_change_rewrites_digits() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.display_value(12).unwrap();
+    display
+        .set_decode_mode(0b0011, as1115::DecodeType::CodeB)
+        .unwrap();
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [
+            as1115::code_b::BLANK,
+            as1115::code_b::BLANK,
+            as1115::NUMBERS[1],
+            as1115::NUMBERS[2]
+        ]
+    );
+
+    display
+        .set_decode_mode(0b0001, as1115::DecodeType::CodeB)
+        .unwrap();
+    assert_eq!(
+        display.i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [
+            as1115::code_b::BLANK,
+            0,
+            as1115::NUMBERS[1],
+            as1115::NUMBERS[2]
+        ]
+    );
+}
+
+#[test]
+fn hex_decoding_rejects_minus() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display
+        .set_decode_mode(0b1111, as1115::DecodeType::Hex)
+        .unwrap();
+    assert!(display.display_hex_value(0xBEEF).is_ok());
+    assert!(display.display_hex_value(-0xF).is_err());
+    assert!(display
+        .set_decode_mode(0b1_0000, as1115::DecodeType::Hex)
+        .is_err());
+}
+
+#[test]
+fn rejected_value_stages_nothing() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display
+        .set_decode_mode(0b0011, as1115::DecodeType::Hex)
+        .unwrap();
+    display.display_value(1234).unwrap();
+    assert!(display.display_value(-567).is_err());
+    display.set_digit_segment_data(3, 0).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [1, 2, as1115::NUMBERS[3], 0]
+    );
+}
+
+#[test]
+fn shutdown_and_wake() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.set_blink(true).unwrap();
+    display.shutdown(true).unwrap();
+    display.wake(false).unwrap();
+    display.set_blink_sync(true).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.registers[register::SHUTDOWN_MODE as usize],
+        register::shutdown_mode::NORMAL_OPERATION | register::shutdown_mode::RESET_FEATURE
+    );
+    // blink enable was reset on wake so only the sync bit remains
+    assert_eq!(
+        i2c.registers[register::FEATURE as usize],
+        register::feature::SYNC
+    );
+}
+
+#[test]
+fn clock_source_and_blink_period() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.set_blink_frequency(BlinkFrequency::Slow).unwrap();
+    assert_eq!(display.blink_period_ms(), Some(2000));
+
+    display.set_clock_source(ClockSource::External).unwrap();
+    assert_eq!(display.clock_source(), ClockSource::External);
+    assert_eq!(display.blink_period_ms(), None);
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.registers[register::FEATURE as usize],
+        register::feature::CLK_EN | register::feature::BLINK_FREQ_SET
+    );
+}
+
+#[test]
+fn reset_registers_reapplies_state() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> = as1115::AS1115::new(RegisterMock::new());
+
+    display.init(5).unwrap();
+    display.set_digit_intensity(1, 9).unwrap();
+    display.display_value(1234).unwrap();
+
+    // simulate corrupted register state
+    display.i2c.registers = [0; 0x30];
+    display.i2c.writes.clear();
+    display.reset_registers(true).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.writes[0].1,
+        [register::FEATURE, register::feature::REG_RESET]
+    );
+    assert_eq!(i2c.registers[register::SCAN_LIMIT as usize], NUM_DIGITS - 1);
+    assert_eq!(i2c.registers[register::DIG01_INTENSITY as usize], 0x95);
+    assert_eq!(
+        i2c.registers[register::DIGIT_OFFSET as usize..][..4],
+        [
+            as1115::NUMBERS[1],
+            as1115::NUMBERS[2],
+            as1115::NUMBERS[3],
+            as1115::NUMBERS[4]
+        ]
+    );
+}
+
+#[test]
+fn reset_registers_reprovisions_address() {
+    let mut display: as1115::AS1115<_, NUM_DIGITS> =
+        as1115::AS1115::new_with_addr(RegisterMock::new(), 0x03);
+
+    display.init(5).unwrap();
+    display.i2c.writes.clear();
+    display.reset_registers(true).unwrap();
+
+    let i2c = display.destroy();
+    assert_eq!(
+        i2c.writes[1],
+        (
+            as1115::DEFAULT_ADDRESS,
+            vec![
+                register::SELF_ADDRESSING,
+                register::self_addressing::USER_SET_ADDR
+            ]
+        )
+    );
+    assert!(i2c.writes[2..].iter().all(|(address, _)| *address == 0x03));
+}
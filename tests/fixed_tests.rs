@@ -0,0 +1,94 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{segments, AS1115, MINUS_SIGN, NUMBERS};
+use common::{digits, RegisterMock};
+
+#[test]
+fn fixed_point_places_dp_and_rounds() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+
+    display.display_fixed(3300, 3).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            NUMBERS[3] | segments::DP,
+            NUMBERS[3],
+            NUMBERS[0],
+            NUMBERS[0]
+        ]
+    );
+
+    // leading zero before the DP
+    display.display_fixed(-5, 2).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            MINUS_SIGN,
+            NUMBERS[0] | segments::DP,
+            NUMBERS[0],
+            NUMBERS[5]
+        ]
+    );
+
+    // decimals that don't fit are rounded off, carrying into the integer part
+    display.display_fixed(-19996, 3).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            MINUS_SIGN,
+            NUMBERS[2],
+            NUMBERS[0] | segments::DP,
+            NUMBERS[0]
+        ]
+    );
+    display.display_fixed(12345, 0).unwrap_err();
+    display.display_fixed(-1000, 0).unwrap_err();
+}
+
+#[test]
+fn fixed_point_edge_sizes() {
+    let mut display: AS1115<_, 1> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.display_fixed(15, 1).unwrap();
+    assert_eq!(digits(&display), &[NUMBERS[2]]);
+    display.display_fixed(-4, 1).unwrap();
+    assert_eq!(digits(&display), &[NUMBERS[0]]);
+    assert!(display.display_fixed(-1, 0).is_err());
+
+    let mut display: AS1115<_, 8> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.display_fixed(i32::MIN, 9).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            MINUS_SIGN,
+            NUMBERS[2] | segments::DP,
+            NUMBERS[1],
+            NUMBERS[4],
+            NUMBERS[7],
+            NUMBERS[4],
+            NUMBERS[8],
+            NUMBERS[4]
+        ]
+    );
+    assert!(display.display_fixed(i32::MAX, 0).is_err());
+
+    // values scaled by many decimals round to zero
+    let zero = [
+        NUMBERS[0] | segments::DP,
+        NUMBERS[0],
+        NUMBERS[0],
+        NUMBERS[0],
+        NUMBERS[0],
+        NUMBERS[0],
+        NUMBERS[0],
+        NUMBERS[0],
+    ];
+    display.display_fixed(i32::MAX, 18).unwrap();
+    assert_eq!(digits(&display), &zero);
+    display.display_fixed(i32::MIN, 200).unwrap();
+    assert_eq!(digits(&display), &zero);
+}
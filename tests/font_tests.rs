@@ -0,0 +1,99 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{
+    ascii_to_segment, register, segments, Font, GlyphTable, LetterCase, AS1115, ASCII_FONT,
+    DEGREE_SIGN, LETTERS, LOWERCASE_LETTERS, MINUS_SIGN, NUMBERS, UPPERCASE_LETTERS,
+};
+use common::RegisterMock;
+use core::fmt::Write;
+
+#[test]
+fn printable_ascii_is_mapped() {
+    for c in 0x20..=0x7E {
+        assert!(
+            ascii_to_segment(c).is_some(),
+            "no glyph for {:?}",
+            c as char
+        );
+    }
+    assert_eq!(ascii_to_segment(0xB0), Some(DEGREE_SIGN));
+    assert_eq!(ascii_to_segment(0x7F), None);
+}
+
+#[test]
+fn status_codes_render_symbols() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    let start = register::DIGIT_OFFSET as usize;
+
+    display.display_ascii(b"E-04").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 4],
+        &[LETTERS[4], MINUS_SIGN, NUMBERS[0], NUMBERS[4]]
+    );
+
+    display.display_ascii(b"Lo_b").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 4],
+        &[LETTERS[11], LETTERS[14], segments::D, LETTERS[1]]
+    );
+}
+
+#[test]
+fn letter_case_mode_distinguishes_pairs() {
+    let mut display: AS1115<_, 2> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    let start = register::DIGIT_OFFSET as usize;
+
+    display.display_ascii(b"Hi").unwrap();
+    let folded = [
+        display.i2c.registers[start],
+        display.i2c.registers[start + 1],
+    ];
+    display.display_ascii(b"hI").unwrap();
+    assert_eq!(&display.i2c.registers[start..start + 2], &folded);
+
+    display.set_letter_case(LetterCase::Sensitive);
+    display.display_ascii(b"Hi").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 2],
+        &[UPPERCASE_LETTERS[7], LOWERCASE_LETTERS[8]]
+    );
+    display.display_ascii(b"hI").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 2],
+        &[LOWERCASE_LETTERS[7], UPPERCASE_LETTERS[8]]
+    );
+    assert_ne!(LOWERCASE_LETTERS[7], UPPERCASE_LETTERS[7]);
+    assert_ne!(LOWERCASE_LETTERS[8], UPPERCASE_LETTERS[8]);
+}
+
+static BRAND_FONT: GlyphTable<2> =
+    GlyphTable::new([('Ж', 0x7F), ('1', segments::E | segments::F)]).with_fallback(&ASCII_FONT);
+
+#[test]
+fn custom_font_overrides_glyphs() {
+    assert_eq!(BRAND_FONT.glyph('Ж'), Some(0x7F));
+    assert_eq!(BRAND_FONT.glyph('2'), Some(NUMBERS[2]));
+    assert_eq!(GlyphTable::new([('x', 0x01)]).glyph('y'), None);
+
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.set_font(&BRAND_FONT);
+    display.set_letter_case(LetterCase::Sensitive); // keeps the custom font
+    let start = register::DIGIT_OFFSET as usize;
+
+    display.display_ascii(b"12").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 2],
+        &[segments::E | segments::F, NUMBERS[2]]
+    );
+
+    write!(display.writer(), "Ж°").unwrap();
+    assert_eq!(
+        &display.i2c.registers[start..start + 4],
+        &[0x7F, DEGREE_SIGN, 0, 0]
+    );
+}
@@ -0,0 +1,150 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{segments, Alignment, FormatOptions, SignPlacement, AS1115, MINUS_SIGN, NUMBERS};
+use common::{digits, RegisterMock};
+
+#[test]
+fn zero_padding_and_sign_placement() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    let zero_padded = FormatOptions::new().zero_pad(true).min_width(4);
+
+    display.display_hex_value_with(0xAB, &zero_padded).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[NUMBERS[0], NUMBERS[0], NUMBERS[10], NUMBERS[11]]
+    );
+
+    display.display_value_with(-5, &zero_padded).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, NUMBERS[0], NUMBERS[0], NUMBERS[5]]
+    );
+
+    let edge = FormatOptions::new().sign(SignPlacement::Edge);
+    display.display_value_with(-5, &edge).unwrap();
+    assert_eq!(digits(&display), &[MINUS_SIGN, 0, 0, NUMBERS[5]]);
+
+    // an edge sign is not part of the zero padded or filled field
+    display
+        .display_value_with(-5, &edge.zero_pad(true))
+        .unwrap();
+    assert_eq!(digits(&display), &[MINUS_SIGN, 0, 0, NUMBERS[5]]);
+    display
+        .display_value_with(-5, &edge.zero_pad(true).min_width(2))
+        .unwrap();
+    assert_eq!(digits(&display), &[MINUS_SIGN, 0, 0, NUMBERS[5]]);
+    display
+        .display_value_with(-5, &edge.zero_pad(true).min_width(4))
+        .unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, NUMBERS[0], NUMBERS[0], NUMBERS[5]]
+    );
+    display
+        .display_value_with(-5, &edge.fill(segments::G).align(Alignment::Left))
+        .unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, NUMBERS[5], segments::G, segments::G]
+    );
+    display.display_value_with(-123, &edge).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, NUMBERS[1], NUMBERS[2], NUMBERS[3]]
+    );
+    assert!(display.display_value_with(-1234, &edge).is_err());
+
+    display
+        .display_fixed_with(-5, 1, &FormatOptions::new().align(Alignment::Left))
+        .unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, NUMBERS[0] | segments::DP, NUMBERS[5], 0]
+    );
+
+    assert!(display
+        .display_value_with(1, &FormatOptions::new().min_width(5))
+        .is_err());
+}
+
+#[test]
+fn alignment_and_fill() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+
+    let center = FormatOptions::new().align(Alignment::Center);
+    display.display_value_with(42, &center).unwrap();
+    assert_eq!(digits(&display), &[0, NUMBERS[4], NUMBERS[2], 0]);
+    display.display_value_with(7, &center).unwrap();
+    assert_eq!(digits(&display), &[0, NUMBERS[7], 0, 0]);
+
+    let dashes = FormatOptions::new().fill(segments::G).min_width(2);
+    display.display_value_with(7, &dashes).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[segments::G, segments::G, segments::G, NUMBERS[7]]
+    );
+}
+
+#[test]
+fn full_integer_ranges() {
+    let mut display: AS1115<_, 8> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+
+    display.display_hex_value(0xFFFF_FFFFu32).unwrap();
+    assert_eq!(digits(&display), &[NUMBERS[15]; 8]);
+    display.display_hex_value(0x8000_0000u64).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            NUMBERS[8], NUMBERS[0], NUMBERS[0], NUMBERS[0], NUMBERS[0], NUMBERS[0], NUMBERS[0],
+            NUMBERS[0]
+        ]
+    );
+    assert!(display.display_hex_value(0x1_0000_0000u64).is_err());
+    assert!(display.display_hex_value(u64::MAX).is_err());
+    assert!(display.display_hex_value(i64::MIN).is_err());
+
+    display.display_value(-9_999_999i64).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            MINUS_SIGN, NUMBERS[9], NUMBERS[9], NUMBERS[9], NUMBERS[9], NUMBERS[9], NUMBERS[9],
+            NUMBERS[9]
+        ]
+    );
+    assert!(display.display_value(-10_000_000i64).is_err());
+    assert!(display.display_value(100_000_000u64).is_err());
+}
+
+#[test]
+fn binary_octal_and_radix() {
+    let mut display: AS1115<_, 8> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    let (zero, one) = (NUMBERS[0], NUMBERS[1]);
+
+    display.display_binary(0b1011_0001u8).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[one, zero, one, one, zero, zero, zero, one]
+    );
+    assert!(display.display_binary(0x100).is_err());
+
+    display.display_binary(-0b101).unwrap();
+    assert_eq!(digits(&display), &[0, 0, 0, 0, MINUS_SIGN, one, zero, one]);
+
+    display.display_octal(0o7654_3210).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[NUMBERS[7], NUMBERS[6], NUMBERS[5], NUMBERS[4], NUMBERS[3], NUMBERS[2], one, zero]
+    );
+    assert!(display.display_octal(0o1_0000_0000).is_err());
+
+    display.display_radix(35, 12).unwrap();
+    assert_eq!(&digits(&display)[6..], &[NUMBERS[2], NUMBERS[11]]);
+    assert!(display.display_radix(1, 1).is_err());
+    assert!(display.display_radix(1, 17).is_err());
+}
@@ -0,0 +1,110 @@
+extern crate as1115;
+
+use as1115::{Key, KeyEvent, KeyLine, KeyManager, KeyState};
+
+fn key(index: u8) -> Key {
+    Key::new(index).unwrap()
+}
+
+#[test]
+fn press_hold_repeat_release() {
+    let mut keys = KeyManager::new(500, 100);
+
+    assert_eq!(keys.update(KeyState::default(), 0).count(), 0);
+    assert_eq!(
+        keys.update(KeyState::from_bits(0x0001), 10)
+            .collect::<Vec<_>>(),
+        [KeyEvent::Pressed(key(0))]
+    );
+    assert_eq!(keys.update(KeyState::from_bits(0x0001), 400).count(), 0);
+    assert_eq!(
+        keys.update(KeyState::from_bits(0x0001), 510)
+            .collect::<Vec<_>>(),
+        [KeyEvent::Held(key(0), 500)]
+    );
+    assert_eq!(keys.update(KeyState::from_bits(0x0001), 550).count(), 0);
+    assert_eq!(
+        keys.update(KeyState::from_bits(0x0001), 610)
+            .collect::<Vec<_>>(),
+        [KeyEvent::Repeat(key(0))]
+    );
+    assert_eq!(
+        keys.update(KeyState::from_bits(0x8000), 620)
+            .collect::<Vec<_>>(),
+        [KeyEvent::Released(key(0)), KeyEvent::Pressed(key(15))]
+    );
+}
+
+#[test]
+fn key_state_from_registers() {
+    // self-addressing clears the KEYA bits of SEGF and SEGG, which must not read as pressed
+    let state = KeyState::from_registers(0x7C, 0xFE, true);
+    let pressed: Vec<_> = state.iter().collect();
+    assert_eq!(
+        pressed,
+        [
+            Key::from_line(KeyLine::KeyB, as1115::segments::G).unwrap(),
+            Key::from_line(KeyLine::KeyA, as1115::segments::DP).unwrap(),
+        ]
+    );
+    assert_eq!(pressed[1].index(), 15);
+    assert_eq!(pressed[1].line(), KeyLine::KeyA);
+
+    let diff = KeyState::from_bits(0x0003).diff(&KeyState::from_bits(0x0006));
+    assert_eq!(diff.pressed, KeyState::from_bits(0x0001));
+    assert_eq!(diff.released, KeyState::from_bits(0x0004));
+}
+
+mod common;
+
+use common::MockDelay;
+
+struct MockIrq(bool);
+
+impl embedded_hal::digital::ErrorType for MockIrq {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::digital::InputPin for MockIrq {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.0)
+    }
+}
+
+#[test]
+fn keys_read_only_on_irq() {
+    let mut i2c = common::RegisterMock::new();
+    i2c.registers[as1115::register::KEY_A as usize] = 0xFE;
+    i2c.registers[as1115::register::KEY_B as usize] = 0xFF;
+
+    let mut display: as1115::AS1115<_, 4, _> = as1115::AS1115::new(i2c).with_irq(MockIrq(true));
+    assert_eq!(display.keys_changed().unwrap(), None);
+
+    let (i2c, _) = display.destroy_with_irq();
+    let mut display: as1115::AS1115<_, 4, _> = as1115::AS1115::new(i2c).with_irq(MockIrq(false));
+    assert_eq!(
+        display.keys_changed().unwrap(),
+        Some(KeyState::from_bits(0x0100))
+    );
+    assert!(display
+        .wait_for_keys(&mut MockDelay(0), 10)
+        .unwrap()
+        .is_pressed(key(8)));
+}
+
+#[test]
+fn wait_for_keys_times_out() {
+    let mut display: as1115::AS1115<_, 4, _> =
+        as1115::AS1115::new(common::RegisterMock::new()).with_irq(MockIrq(true));
+    let mut delay = MockDelay(0);
+    assert!(matches!(
+        display.wait_for_keys(&mut delay, 10),
+        Err(as1115::AS1115Error::Timeout)
+    ));
+    assert_eq!(delay.0, 10_000_000);
+    assert!(display.i2c.writes.is_empty());
+}
@@ -0,0 +1,114 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{
+    register, segments, DecodeType, DigitMap, Rotation, SegmentMap, WriteMode, AS1115, MINUS_SIGN,
+    NUMBERS,
+};
+use common::{digits, RegisterMock};
+
+#[test]
+fn segment_map_rewires_glyphs() {
+    let swapped = SegmentMap::IDENTITY.swap(segments::B, segments::F);
+    assert_eq!(
+        SegmentMap::from_wiring([
+            segments::DP,
+            segments::A,
+            segments::F,
+            segments::C,
+            segments::D,
+            segments::E,
+            segments::B,
+            segments::G,
+        ]),
+        Some(swapped)
+    );
+    assert_eq!(SegmentMap::from_wiring([segments::A; 8]), None);
+
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.display_ascii(b"-1.").unwrap();
+    display.set_segment_map(swapped).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[MINUS_SIGN, segments::F | segments::C | segments::DP, 0, 0]
+    );
+
+    // hardware decoded digits are left to the decoder
+    display.set_decode_mode(0x08, DecodeType::CodeB).unwrap();
+    display.display_value(17).unwrap();
+    assert_eq!(digits(&display), &[0, 0, swapped.apply(NUMBERS[1]), 7]);
+}
+
+#[test]
+fn digit_map_reorders_registers() {
+    assert_eq!(DigitMap::new(&[1, 0, 3, 2]).map(|m| m.digit(2)), Some(3));
+    assert_eq!(DigitMap::new(&[0, 2]), None);
+    assert_eq!(DigitMap::new(&[1, 1]), None);
+
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.display_value(42).unwrap();
+    display.set_digit_intensity(3, 9).unwrap();
+    assert!(display.set_digit_map(DigitMap::reversed(8)).is_err());
+
+    // existing digits and intensities move to their new registers
+    display.set_digit_map(DigitMap::reversed(4)).unwrap();
+    assert_eq!(digits(&display), &[NUMBERS[2], NUMBERS[4], 0, 0]);
+    assert_eq!(
+        display.i2c.registers[register::DIG01_INTENSITY as usize],
+        0x39
+    );
+
+    display
+        .set_digit_map(DigitMap::new(&[2, 0, 3, 1]).unwrap())
+        .unwrap();
+    display.display_ascii(b"1.234").unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            NUMBERS[2],
+            NUMBERS[4],
+            NUMBERS[1] | segments::DP,
+            NUMBERS[3]
+        ]
+    );
+}
+
+#[test]
+fn upside_down_rotates_digits() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+    display.set_write_mode(WriteMode::Buffered);
+    display.set_rotation(Rotation::UpsideDown).unwrap();
+
+    // digit order is reversed and the DP is shown on the following digit
+    display.display_ascii(b"1.2").unwrap();
+    display.set_digit_value(3, 7).unwrap();
+    display.flush().unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            segments::D | segments::E | segments::F,
+            0,
+            NUMBERS[2] | segments::DP,
+            segments::E | segments::F
+        ]
+    );
+
+    // removing the DP rewrites the digit showing it
+    display.set_digit_segment_data(0, NUMBERS[1]).unwrap();
+    display.flush().unwrap();
+    assert_eq!(digits(&display)[2], NUMBERS[2]);
+
+    // the decoder font can't be rotated
+    assert!(display.set_decode_mode(0x01, DecodeType::CodeB).is_err());
+
+    display.set_rotation(Rotation::Normal).unwrap();
+    display.flush().unwrap();
+    assert_eq!(digits(&display), &[NUMBERS[1], NUMBERS[2], 0, NUMBERS[7]]);
+
+    display.set_decode_mode(0x01, DecodeType::CodeB).unwrap();
+    assert!(display.set_rotation(Rotation::UpsideDown).is_err());
+}
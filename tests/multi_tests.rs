@@ -0,0 +1,131 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{
+    register, segments, AS1115Multi, VirtualDisplay, WriteMode, DEFAULT_ADDRESS, MINUS_SIGN,
+    NUMBERS,
+};
+use common::RegisterMock;
+
+#[test]
+fn provisions_and_addresses_chips() {
+    let mut displays: AS1115Multi<_, 4, 2> = AS1115Multi::new(RegisterMock::new(), [0x01, 0x02]);
+
+    displays.init(3).unwrap();
+    displays
+        .with_chip(1, |chip| chip.display_value(42))
+        .unwrap();
+    assert!(displays.with_chip(2, |chip| chip.clear()).is_err());
+    assert!(matches!(
+        displays.with_chip(256, |chip| chip.clear()),
+        Err(as1115::AS1115Error::InvalidLocation(255))
+    ));
+
+    let i2c = displays.destroy();
+    // self-addressing is broadcast once to the factory address
+    assert_eq!(
+        i2c.writes
+            .iter()
+            .filter(|(address, _)| *address == DEFAULT_ADDRESS)
+            .count(),
+        2
+    );
+    assert!(i2c
+        .writes
+        .contains(&(0x01, vec![register::GLOBAL_INTENSITY, 3])));
+    assert!(i2c
+        .writes
+        .contains(&(0x02, vec![register::GLOBAL_INTENSITY, 3])));
+    assert_eq!(
+        i2c.writes.last().unwrap(),
+        &(
+            0x02,
+            vec![
+                register::DIGIT_OFFSET,
+                0,
+                0,
+                as1115::NUMBERS[4],
+                as1115::NUMBERS[2]
+            ]
+        )
+    );
+}
+
+#[test]
+fn virtual_display_spans_chips() {
+    let mut multi: AS1115Multi<_, 4, 2> = AS1115Multi::new(RegisterMock::new(), [0x01, 0x02]);
+    multi.init(3).unwrap();
+    let mut display = VirtualDisplay::new(multi);
+
+    assert!(display.display_value(-12345678).is_err());
+    assert!(display.display_value(99999999).is_ok());
+    assert!(display.display_binary(0xFFu8).is_ok());
+    assert!(display.display_binary(0x100).is_err());
+    // minus sign on the first chip, remaining digits split across the boundary
+    display.display_value(-1234567).unwrap();
+    display.set_digit_intensity(5, 2).unwrap();
+    assert!(display.set_digit_intensity(8, 2).is_err());
+
+    let i2c = display.destroy().destroy();
+    assert!(i2c.writes.contains(&(
+        0x01,
+        vec![
+            register::DIGIT_OFFSET,
+            MINUS_SIGN,
+            NUMBERS[1],
+            NUMBERS[2],
+            NUMBERS[3]
+        ]
+    )));
+    assert!(i2c.writes.contains(&(
+        0x02,
+        vec![
+            register::DIGIT_OFFSET,
+            NUMBERS[4],
+            NUMBERS[5],
+            NUMBERS[6],
+            NUMBERS[7]
+        ]
+    )));
+    assert_eq!(
+        i2c.writes.last().unwrap(),
+        &(0x02, vec![register::DIG01_INTENSITY, 0x23])
+    );
+}
+
+#[test]
+fn virtual_display_scrolls_ascii() {
+    let mut multi: AS1115Multi<_, 2, 2> = AS1115Multi::new(RegisterMock::new(), [0x01, 0x02]);
+    multi.set_write_mode(WriteMode::Buffered);
+    let mut display = VirtualDisplay::new(multi);
+
+    // DP attaches to the preceding digit across the chip boundary
+    display.display_ascii_at(b"12.3", 1).unwrap();
+    display.flush().unwrap();
+    assert_eq!(
+        display.multi().i2c.writes,
+        vec![
+            (0x01, vec![register::DIGIT_OFFSET + 1, NUMBERS[1]]),
+            (
+                0x02,
+                vec![
+                    register::DIGIT_OFFSET,
+                    NUMBERS[2] | segments::DP,
+                    NUMBERS[3]
+                ]
+            ),
+        ]
+    );
+
+    display.multi_mut().i2c.writes.clear();
+    display.display_ascii_at(b"12.3", -2).unwrap();
+    display.flush().unwrap();
+    assert_eq!(
+        display.multi().i2c.writes,
+        vec![
+            (0x01, vec![register::DIGIT_OFFSET, NUMBERS[3], 0]),
+            (0x02, vec![register::DIGIT_OFFSET, 0, 0]),
+        ]
+    );
+}
@@ -0,0 +1,53 @@
+extern crate as1115;
+
+mod common;
+
+use as1115::{segments, AS1115, NUMBERS};
+use common::{digits, RegisterMock};
+use core::fmt::Write;
+
+#[test]
+fn writer_formats_and_blanks_unused_digits() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+
+    write!(display.writer(), "{:>4}", 42).unwrap();
+    assert_eq!(digits(&display), &[0, 0, NUMBERS[4], NUMBERS[2]]);
+
+    // '.' merges into the previous digit, even across write_str calls
+    let mut writer = display.writer();
+    write!(writer, "{}", 1).unwrap();
+    write!(writer, ".{}", 5).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        digits(&display),
+        &[NUMBERS[1] | segments::DP, NUMBERS[5], 0, 0]
+    );
+
+    // text past the last digit is dropped
+    write!(display.writer(), "{:.4}", 3.14259).unwrap();
+    assert_eq!(
+        digits(&display),
+        &[
+            NUMBERS[3] | segments::DP,
+            NUMBERS[1],
+            NUMBERS[4],
+            NUMBERS[2]
+        ]
+    );
+}
+
+#[cfg(feature = "ufmt")]
+#[test]
+fn writer_implements_uwrite() {
+    let mut display: AS1115<_, 4> = AS1115::new(RegisterMock::new());
+    display.init(3).unwrap();
+
+    let mut writer = display.writer();
+    ufmt_write::uWrite::write_str(&mut writer, "1.2").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        digits(&display),
+        &[NUMBERS[1] | segments::DP, NUMBERS[2], 0, 0]
+    );
+}